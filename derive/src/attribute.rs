@@ -1,12 +1,111 @@
 use crate::field::Fields;
 
-use helpers::get_mod_path;
+use helpers::{get_lit_str, get_mod_path, Symbol};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{Data, DeriveInput, Error, Ident};
+use syn::{Attribute, Data, DeriveInput, Error, Ident, Lit, Meta, NestedMeta, Path};
+
+/// Options of `#[annotation(...)]` on an annotation structure.
+#[derive(Default)]
+pub struct AnnotationOptions {
+    pub name: Option<String>,
+    pub repeatable: bool,
+    pub unique: bool,
+}
+
+impl AnnotationOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut options = AnnotationOptions::default();
+        for attr in attrs.iter() {
+            if attr.path == Symbol::new("annotation") {
+                match &attr.parse_meta()? {
+                    Meta::List(list) => {
+                        for nested_item in &list.nested {
+                            match nested_item {
+                                NestedMeta::Meta(Meta::NameValue(name))
+                                    if (name.path == Symbol::new("name")) =>
+                                {
+                                    options.name = Some(Self::get_name(&name.lit)?);
+                                }
+                                NestedMeta::Meta(Meta::Path(path))
+                                    if (path == Symbol::new("repeatable")) =>
+                                {
+                                    options.repeatable = true;
+                                }
+                                NestedMeta::Meta(Meta::Path(path))
+                                    if (path == Symbol::new("unique")) =>
+                                {
+                                    options.unique = true;
+                                }
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        nested_item,
+                                        "Unexpected nested meta",
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "The meta of annotation must be a List",
+                        ));
+                    }
+                }
+            }
+        }
+
+        match options.repeatable && options.unique {
+            true => Err(Error::new_spanned(
+                attrs.first(),
+                "Annotation can't be both repeatable and unique",
+            )),
+            false => Ok(options),
+        }
+    }
+
+    pub fn get_implement(&self) -> TokenStream {
+        let repeatable = match self.repeatable {
+            true => quote! {
+                fn is_repeatable() -> bool {
+                    true
+                }
+            },
+            false => TokenStream::new(),
+        };
+        let unique = match self.unique {
+            true => quote! {
+                fn is_unique() -> bool {
+                    true
+                }
+            },
+            false => TokenStream::new(),
+        };
+
+        quote! {
+            #repeatable
+            #unique
+        }
+    }
+
+    fn get_name(lit: &Lit) -> Result<String, Error> {
+        let name = get_lit_str(lit, &format_ident!("name"))?;
+        let path: Path = syn::parse_str(name.as_str())
+            .map_err(|_| Error::new_spanned(lit, "The name of annotation must be a path"))?;
+
+        Ok(path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"))
+    }
+}
 
 pub struct Annotation {
     ident: Ident,
+    options: AnnotationOptions,
     path: String,
     fields: Fields,
     mod_path: Option<TokenStream>,
@@ -16,16 +115,21 @@ impl Annotation {
     pub fn from_ast(input: &DeriveInput) -> Result<Self, Error> {
         match &input.data {
             Data::Struct(data_struct) => {
-                let path = input.ident.to_string();
+                let options = AnnotationOptions::from_attrs(&input.attrs)?;
+                let path = options
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| input.ident.to_string());
 
                 Ok(Annotation {
                     ident: input.ident.clone(),
+                    options,
                     path,
                     fields: Fields::from_ast(&data_struct.fields)?,
                     mod_path: get_mod_path(&input.attrs)?,
                 })
             }
-            _ => Err(Error::new_spanned(&input, "Attribute must be a struct")),
+            _ => Err(Error::new_spanned(input, "Attribute must be a struct")),
         }
     }
 
@@ -33,8 +137,7 @@ impl Annotation {
         let name = self.ident.clone();
         let from_attributes_args = self
             .fields
-            .parse_attributes_args_token_stream(format_ident!("input"), name.clone());
-        let from_meta = self.fields.parse_meta_token_stream(&self.ident);
+            .parse_attributes_args_token_stream(format_ident!("__annotation_input"), name.clone());
         let path = self.path.clone();
         let to_token_temp_value = self.fields.get_to_token_temp_value_token_stream();
         let struct_path = match &self.mod_path {
@@ -43,7 +146,9 @@ impl Annotation {
             },
             None => name.to_token_stream(),
         };
+        let type_path = struct_path.to_string();
         let to_token = self.fields.get_to_token_token_stream(struct_path);
+        let options = self.options.get_implement();
 
         quote! {
             impl annotation_rs::AnnotationStructure for #name {
@@ -51,15 +156,17 @@ impl Annotation {
                     annotation_rs::Symbol::new(#path)
                 }
 
-                fn from_meta(
-                    input: &syn::Meta
-                ) -> Result<Self, syn::Error>
-                where
-                    Self: std::marker::Sized {
-                    #from_meta
-                }
+                const PATH: Option<&'static str> = Some(#path);
+
+                const TYPE_PATH: Option<&'static str> = Some(#type_path);
+
+                #options
 
-                fn from_attribute_args(input: syn::AttributeArgs) -> Result<Self, syn::Error>
+                fn from_nested_meta_lenient(
+                    __annotation_input: &[annotation_rs::meta::NestedMeta],
+                    __annotation_context: &annotation_rs::AnnotationContext,
+                    __annotation_span: proc_macro2::Span
+                ) -> (Option<Self>, Vec<annotation_rs::Error>)
                 where
                     Self: std::marker::Sized {
                     #from_attributes_args
@@ -68,9 +175,14 @@ impl Annotation {
 
             impl syn::parse_macro_input::ParseMacroInput for #name {
                 fn parse(input: syn::parse::ParseStream) -> Result<Self, syn::Error> {
-                    let attribute_args = syn::AttributeArgs::parse(input)?;
+                    let tokens: proc_macro2::TokenStream = input.parse()?;
                     use annotation_rs::AnnotationStructure;
-                    Self::from_attribute_args(attribute_args)
+                    Self::parse_nested_meta(
+                        &annotation_rs::meta::parse_args(tokens)?,
+                        &annotation_rs::AnnotationContext::default(),
+                        proc_macro2::Span::call_site()
+                    )
+                    .map_err(annotation_rs::to_syn_error)
                 }
             }
 
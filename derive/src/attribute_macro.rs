@@ -0,0 +1,86 @@
+use crate::reader::{Interpolated, InterpolatedList};
+
+use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseBuffer};
+use syn::token::Comma;
+use syn::{Error, Ident, Path, Token};
+
+/// Config of `generate_attribute!(route, Route, handler = my_fn)`.
+pub struct AttributeMacroConfig {
+    pub name: Ident,
+    pub annotation: Path,
+    pub handler: Path,
+}
+
+impl Parse for AttributeMacroConfig {
+    fn parse<'a>(input: &'a ParseBuffer<'a>) -> Result<Self, Error> {
+        let name = input.parse()?;
+        input.parse::<Comma>()?;
+        let annotation = input.parse()?;
+        input.parse::<Comma>()?;
+        let key: Ident = input.parse()?;
+        if key != "handler" {
+            return Err(Error::new_spanned(key, "Expected `handler = ...`"));
+        }
+        input.parse::<Token![=]>()?;
+        let handler = input.parse()?;
+        input.parse::<Option<Comma>>()?;
+
+        Ok(AttributeMacroConfig {
+            name,
+            annotation,
+            handler,
+        })
+    }
+}
+
+impl AttributeMacroConfig {
+    pub fn get_attribute_macro(&self) -> TokenStream {
+        let name = &self.name;
+        let annotation = &self.annotation;
+        let handler = &self.handler;
+        let original_interpolated = Interpolated::new("original");
+        let compile_error_interpolated = Interpolated::new("compile_error");
+        let output_interpolated = Interpolated::new("output");
+        let warnings_interpolated = InterpolatedList::new("warnings", None);
+
+        quote::quote! {
+            #[proc_macro_attribute]
+            pub fn #name(
+                args: proc_macro::TokenStream,
+                input: proc_macro::TokenStream
+            ) -> proc_macro::TokenStream {
+                use annotation_rs::AnnotationStructure;
+                let original = proc_macro2::TokenStream::from(input.clone());
+                let item = syn::parse_macro_input!(input as syn::Item);
+                let context = annotation_rs::AnnotationContext::new()
+                    .with_item(annotation_rs::ItemRef::Item(&item).ident());
+
+                let annotation = annotation_rs::meta::parse_args(proc_macro2::TokenStream::from(args))
+                    .map_err(annotation_rs::Error::from_syn)
+                    .and_then(|nested| <#annotation as AnnotationStructure>::parse_nested_meta(
+                        &nested,
+                        &context,
+                        proc_macro2::Span::call_site()
+                    ));
+                let annotation = match annotation {
+                    Ok(annotation) => annotation,
+                    Err(errors) => {
+                        let compile_error = annotation_rs::to_syn_error(errors).to_compile_error();
+                        return proc_macro::TokenStream::from(quote::quote! {
+                            #original_interpolated
+                            #compile_error_interpolated
+                        });
+                    }
+                };
+                let warnings = context.take_warnings();
+                let output = proc_macro2::TokenStream::from(#handler(annotation, item));
+
+                proc_macro::TokenStream::from(quote::quote! {
+                    #output_interpolated
+                    #warnings_interpolated
+                })
+            }
+        }
+    }
+}
@@ -23,7 +23,7 @@ impl EnumItem {
                             Error::new_spanned(&list, "Unexpected nested segment"),
                         )? {
                             NestedMeta::Lit(lit) => {
-                                value = get_lit_str(&lit, &attr.path.get_ident().unwrap())?
+                                value = get_lit_str(lit, &attr.path.get_ident().unwrap())?
                             }
                             _ => {
                                 return Err(Error::new_spanned(
@@ -72,11 +72,8 @@ impl EnumValue {
     pub fn from_ast(input: &DeriveInput) -> Result<Self, Error> {
         match &input.data {
             Data::Enum(enum_ast) => {
-                let items: Result<Vec<EnumItem>, Error> = enum_ast
-                    .variants
-                    .iter()
-                    .map(|item_ast| EnumItem::from_ast(item_ast))
-                    .collect();
+                let items: Result<Vec<EnumItem>, Error> =
+                    enum_ast.variants.iter().map(EnumItem::from_ast).collect();
 
                 Ok(EnumValue {
                     ident: input.ident.clone(),
@@ -107,10 +104,12 @@ impl EnumValue {
             None => enum_ident.to_token_stream(),
         };
 
+        let values: Vec<&str> = self.items.iter().map(|item| item.value.as_str()).collect();
+
         let to_token_arms: Vec<TokenStream> = self
             .items
             .iter()
-            .map(|item| item.to_token_pattern_arm(&enum_path, &enum_ident))
+            .map(|item| item.to_token_pattern_arm(&enum_path, enum_ident))
             .collect();
         quote! {
             impl std::str::FromStr for #enum_ident {
@@ -118,11 +117,11 @@ impl EnumValue {
                 fn from_str(value: &str) -> Result<Self, Self::Err> {
                     match value {
                         #(#arms,)*
-                        others => Err(
-                            annotation_rs::Error::new(
-                                format!("Unexpected {} value: {}", #enum_name, others),
-                            )
-                        )
+                        others => Err(annotation_rs::Error::invalid_enum_value(
+                            #enum_name,
+                            others,
+                            annotation_rs::suggest(others, &[#((#values, #values)),*])
+                        ))
                     }
                 }
             }
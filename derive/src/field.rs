@@ -1,18 +1,63 @@
-use super::ty::{DefaultValue, FieldType};
+use super::ty::{DefaultValue, FieldType, Type};
 
 use crate::reader::Interpolated;
 use helpers::{get_lit_as_string, get_lit_bool, get_lit_str, Symbol};
 use proc_macro2::TokenStream;
-use quote::format_ident;
+use quote::{format_ident, ToTokens};
 use syn::{
-    Attribute as SynAttribute, Error, Field as SynField, Fields as SynFields, Ident, Index, Meta,
-    NestedMeta,
+    Attribute as SynAttribute, Error, Field as SynField, Fields as SynFields, Ident, Index, Lit,
+    Meta, NestedMeta,
 };
 
 struct FieldAttribute {
     pub path: Option<String>,
     pub enum_value: Option<bool>,
-    pub default: Option<String>,
+    pub default: Option<(String, Lit)>,
+    pub default_from: Option<String>,
+    pub case: Option<String>,
+    pub default_from_field: Option<String>,
+    pub default_with: Option<String>,
+    pub deprecated: Option<String>,
+}
+
+impl FieldAttribute {
+    fn get_default(self, input: &SynField, ty: &Type) -> Result<Option<DefaultValue>, Error> {
+        if self.case.is_some() && self.default_from.is_none() {
+            return Err(Error::new_spanned(
+                input,
+                "case can only be used with default_from",
+            ));
+        }
+
+        if self.default_with.is_some() && self.default_from_field.is_none() {
+            return Err(Error::new_spanned(
+                input,
+                "default_with can only be used with default_from_field",
+            ));
+        }
+
+        match (self.default, self.default_from, self.default_from_field) {
+            (Some((default, lit)), None, None) => {
+                Ok(Some(DefaultValue::from_string(default, &lit, input, ty)?))
+            }
+            (None, Some(default_from), None) => Ok(Some(DefaultValue::from_context(
+                default_from,
+                self.case,
+                input,
+                ty,
+            )?)),
+            (None, None, Some(default_from_field)) => Ok(Some(DefaultValue::from_field(
+                default_from_field,
+                self.default_with,
+                input,
+            )?)),
+            (None, None, None) => Ok(None),
+            _ => Err(Error::new_spanned(
+                input,
+                "default, default_from and default_from_field can not be used together",
+            )),
+        }
+    }
 }
 
 trait ValuedField {
@@ -21,6 +66,11 @@ trait ValuedField {
             path: None,
             enum_value: None,
             default: None,
+            default_from: None,
+            case: None,
+            default_from_field: None,
+            default_with: None,
+            deprecated: None,
         };
         for attr in attrs.iter() {
             if attr.path == Symbol::new("field") {
@@ -47,9 +97,53 @@ trait ValuedField {
                                 NestedMeta::Meta(Meta::NameValue(default))
                                     if (default.path == Symbol::new("default")) =>
                                 {
-                                    attribute.default = Some(get_lit_as_string(
-                                        &default.lit,
-                                        &default.path.get_ident().unwrap(),
+                                    attribute.default = Some((
+                                        get_lit_as_string(
+                                            &default.lit,
+                                            &default.path.get_ident().unwrap(),
+                                        )?,
+                                        default.lit.clone(),
+                                    ));
+                                }
+                                NestedMeta::Meta(Meta::NameValue(default_from))
+                                    if (default_from.path == Symbol::new("default_from")) =>
+                                {
+                                    attribute.default_from = Some(get_lit_str(
+                                        &default_from.lit,
+                                        &default_from.path.get_ident().unwrap(),
+                                    )?);
+                                }
+                                NestedMeta::Meta(Meta::NameValue(case))
+                                    if (case.path == Symbol::new("case")) =>
+                                {
+                                    attribute.case = Some(get_lit_str(
+                                        &case.lit,
+                                        &case.path.get_ident().unwrap(),
+                                    )?);
+                                }
+                                NestedMeta::Meta(Meta::NameValue(default_from_field))
+                                    if (default_from_field.path
+                                        == Symbol::new("default_from_field")) =>
+                                {
+                                    attribute.default_from_field = Some(get_lit_str(
+                                        &default_from_field.lit,
+                                        &default_from_field.path.get_ident().unwrap(),
+                                    )?);
+                                }
+                                NestedMeta::Meta(Meta::NameValue(default_with))
+                                    if (default_with.path == Symbol::new("default_with")) =>
+                                {
+                                    attribute.default_with = Some(get_lit_str(
+                                        &default_with.lit,
+                                        &default_with.path.get_ident().unwrap(),
+                                    )?);
+                                }
+                                NestedMeta::Meta(Meta::NameValue(deprecated))
+                                    if (deprecated.path == Symbol::new("deprecated")) =>
+                                {
+                                    attribute.deprecated = Some(get_lit_str(
+                                        &deprecated.lit,
+                                        &deprecated.path.get_ident().unwrap(),
                                     )?);
                                 }
                                 _ => {
@@ -77,23 +171,105 @@ trait ValuedField {
 
     fn get_default(&self) -> &Option<DefaultValue>;
 
+    fn get_deprecated(&self) -> &Option<String>;
+
+    fn get_warning_token_stream(&self, nested_ident: &Ident) -> TokenStream {
+        let key_description = self.get_key_description();
+        let field_nested_type = self.field_nested_type();
+        let span = quote::quote! { syn::spanned::Spanned::span(&#nested_ident) };
+        let deprecated = self.get_deprecated().as_ref().map(|note| {
+            let message = format!("{} is deprecated: {}", key_description, note);
+            quote::quote! {
+                __annotation_context.warn(annotation_rs::Warning::new(#message).with_span(#span));
+            }
+        });
+        let redundant = self
+            .get_default()
+            .as_ref()
+            .filter(|default| default.is_constant())
+            .map(|default| {
+                let default_token = default.get_token_stream();
+                let message = format!(
+                    "Redundant value for {}, it is the same as the default",
+                    key_description
+                );
+                quote::quote! {
+                    let default_value: Option<#field_nested_type> = #default_token;
+                    if default_value.as_ref() == Some(&value) {
+                        __annotation_context.warn(annotation_rs::Warning::new(#message).with_span(#span));
+                    }
+                }
+            });
+
+        quote::quote! {
+            #deprecated
+            #redundant
+        }
+    }
+
     fn get_temp_var_token_stream(&self) -> TokenStream {
         let temp_var_name = self.get_temp_var_name();
         let field_nested_type = self.field_nested_type();
-        let default_token = match self.get_default() {
-            Some(value) => {
-                let value_string = value.to_string();
-                quote::quote! {Some(#value_string.parse().unwrap())}
-            }
-            None => quote::quote! {None},
-        };
         quote::quote! {
-            let mut #temp_var_name: Option<#field_nested_type> = #default_token
+            let mut #temp_var_name: Option<#field_nested_type> = None
         }
     }
 
+    fn get_resolve_default_token_stream(&self) -> Option<TokenStream> {
+        let temp_var_name = self.get_temp_var_name();
+        self.get_default().as_ref().map(|default| {
+            let default_token = default.get_token_stream();
+            quote::quote! {
+                if #temp_var_name.is_none() {
+                    #temp_var_name = #default_token;
+                }
+            }
+        })
+    }
+
     fn get_parse_token_stream(&self) -> TokenStream;
 
+    fn get_required_check_token_stream(&self) -> Option<TokenStream> {
+        let temp_var_name = self.get_temp_var_name();
+        let temp_var_name_str = temp_var_name.to_string();
+        let key_description = self.get_key_description();
+        match self.is_required() {
+            true => Some(quote::quote! {
+                if #temp_var_name.is_none()
+                    && !__annotation_invalid_fields.contains(#temp_var_name_str)
+                {
+                    __annotation_missing_fields.push(#key_description);
+                }
+            }),
+            false => None,
+        }
+    }
+
+    fn is_required(&self) -> bool;
+
+    fn has_default(&self) -> bool;
+
+    /// Unwrap the temp var of a required field, falling back to `Default` when the value is
+    /// invalid or missing. Without a `Default` no structure can be built at all.
+    fn get_unwrap_token_stream(&self) -> Option<TokenStream> {
+        let temp_var_name = self.get_temp_var_name();
+        let fallback = match self.has_default() {
+            true => quote::quote! { Default::default() },
+            false => quote::quote! { return (None, __annotation_errors.into_errors()) },
+        };
+        match self.is_required() {
+            true => Some(quote::quote! {
+                let #temp_var_name = match #temp_var_name {
+                    Some(value) => value,
+                    None => #fallback,
+                };
+            }),
+            false => None,
+        }
+    }
+
+    fn get_key_description(&self) -> String;
+
     fn get_construct_token_stream(&self) -> TokenStream;
 }
 
@@ -101,12 +277,13 @@ pub struct NamedField {
     name: Ident,
     path: String,
     default: Option<DefaultValue>,
+    deprecated: Option<String>,
     field_type: FieldType,
 }
 
 impl ValuedField for NamedField {
     fn get_temp_var_name(&self) -> Ident {
-        self.name.clone()
+        get_field_var_name(&self.name)
     }
 
     fn field_nested_type(&self) -> TokenStream {
@@ -117,15 +294,20 @@ impl ValuedField for NamedField {
         &self.default
     }
 
+    fn get_deprecated(&self) -> &Option<String> {
+        &self.deprecated
+    }
+
     fn get_parse_token_stream(&self) -> TokenStream {
         let temp_var_name = self.get_temp_var_name();
+        let temp_var_name_str = temp_var_name.to_string();
         let path_name = self.path.as_str();
-        let nested_ident = format_ident!("nested_{}", temp_var_name);
+        let nested_ident = format_ident!("__nested_{}", self.name);
         let nested_pattern = self
             .field_type
             .unwrap()
             .get_nested_pattern(true, &nested_ident);
-        let nested_lit = quote::quote! { #nested_ident.lit };
+        let nested_lit = quote::quote! { #nested_ident.value.to_lit() };
         let path = quote::quote! { String::from(#path_name) };
         let reader = self.field_type.unwrap().get_lit_reader(
             &nested_ident,
@@ -134,73 +316,122 @@ impl ValuedField for NamedField {
             &nested_ident,
         );
 
+        let warning = self.get_warning_token_stream(&nested_ident);
+        if let Type::Object(_) = self.field_type.unwrap() {
+            // objects are parsed leniently as well, so that a partial object is kept
+            let object_type = self.field_nested_type();
+            return quote::quote! {
+                #nested_pattern if #nested_ident.key() == annotation_rs::Symbol::new(#path_name) => {
+                    let (value, nested_errors) = match #nested_ident.nested() {
+                        Ok(__annotation_input) => #object_type::from_nested_meta_lenient(
+                            __annotation_input,
+                            __annotation_context,
+                            syn::spanned::Spanned::span(#nested_ident)
+                        ),
+                        Err(e) => (None, annotation_rs::Error::from_syn(e)),
+                    };
+                    if !nested_errors.is_empty() {
+                        __annotation_errors.push(annotation_rs::IntoErrors::into_errors_at(
+                            nested_errors,
+                            #path_name
+                        ));
+                        __annotation_invalid_fields.insert(#temp_var_name_str);
+                    }
+                    if let Some(value) = value {
+                        #warning
+                        #temp_var_name = Some(value)
+                    }
+                }
+            };
+        }
         let path_ident = self.field_type.unwrap().get_path_ident(nested_ident);
         quote::quote! {
             #nested_pattern if #path_ident == annotation_rs::Symbol::new(#path_name) => {
-                #temp_var_name = Some(#reader?);
+                match #reader {
+                    Ok(value) => {
+                        #warning
+                        #temp_var_name = Some(value)
+                    },
+                    Err(e) => {
+                        __annotation_errors.push(e);
+                        __annotation_invalid_fields.insert(#temp_var_name_str);
+                    }
+                }
             }
         }
     }
 
+    fn is_required(&self) -> bool {
+        self.field_type.is_required()
+    }
+
+    fn has_default(&self) -> bool {
+        self.field_type.unwrap().has_default()
+    }
+
+    fn get_key_description(&self) -> String {
+        match self.name == self.path.as_str() {
+            true => format!("`{}`", self.path),
+            false => format!("`{}` (field `{}`)", self.path, self.name),
+        }
+    }
+
     fn get_construct_token_stream(&self) -> TokenStream {
         let field_name = self.name.clone();
         let temp_var_name = self.get_temp_var_name();
-        match self.field_type.is_required() {
-            true => quote::quote! {
-                #field_name: match #temp_var_name {
-                    Some(value) => Ok(value),
-                    None => Err(syn::Error::new_spanned(
-                        quote::format_ident!{"Attribute"},
-                        "Not found value on required field"
-                    ))
-                }?
-            },
-            false => quote::quote! {
-                #temp_var_name
-            },
+        quote::quote! {
+            #field_name: #temp_var_name
         }
     }
 }
 
 impl NamedField {
     pub fn from_ast(input: &SynField) -> Result<Self, Error> {
-        let attribute = Self::get_attribute(&input.attrs)?;
-        let mut path = input.ident.as_ref().unwrap().to_string();
-        if attribute.path.is_some() {
-            path = attribute.path.unwrap();
-        }
+        let mut attribute = Self::get_attribute(&input.attrs)?;
+        let path = match attribute.path.take() {
+            Some(path) => path,
+            None => input.ident.as_ref().unwrap().to_string(),
+        };
 
-        let mut is_enum = false;
-        if attribute.enum_value.is_some() {
-            is_enum = attribute.enum_value.unwrap()
-        }
+        let is_enum = attribute.enum_value.unwrap_or(false);
         let field_type = FieldType::from_ast(&input.ty, is_enum)?;
-        let mut default: Option<DefaultValue> = None;
-        if attribute.default.is_some() {
-            default = Some(DefaultValue::from_string(
-                attribute.default.unwrap(),
-                input,
-                &field_type.unwrap(),
-            )?)
-        }
+        let deprecated = attribute.deprecated.take();
+        let default = attribute.get_default(input, field_type.unwrap())?;
         Ok(NamedField {
             name: input.ident.as_ref().unwrap().clone(),
             path,
             default,
+            deprecated,
             field_type,
         })
     }
 }
 
+/// The generated parsers keep the value of a field in a local named after it, prefixed so that
+/// fields never clash with the other locals of the generated code.
+pub fn get_field_var_name(name: &Ident) -> Ident {
+    format_ident!("__field_{}", name)
+}
+
+fn ordinal(number: usize) -> String {
+    match number {
+        1 => String::from("1st"),
+        2 => String::from("2nd"),
+        3 => String::from("3rd"),
+        others => format!("{}th", others),
+    }
+}
+
 pub struct UnnamedFiled {
     index: usize,
     field_type: FieldType,
     default: Option<DefaultValue>,
+    deprecated: Option<String>,
 }
 
 impl ValuedField for UnnamedFiled {
     fn get_temp_var_name(&self) -> Ident {
-        format_ident!("temp_{:0}", self.index)
+        format_ident!("__field_{}", self.index)
     }
 
     fn field_nested_type(&self) -> TokenStream {
@@ -211,25 +442,22 @@ impl ValuedField for UnnamedFiled {
         &self.default
     }
 
+    fn get_deprecated(&self) -> &Option<String> {
+        &self.deprecated
+    }
+
     fn get_parse_token_stream(&self) -> TokenStream {
         let temp_var_name = self.get_temp_var_name();
-        let nested_ident = format_ident!("nested_{}", temp_var_name);
+        let temp_var_name_str = temp_var_name.to_string();
+        let nested_ident = format_ident!("__nested_{}", self.index);
         let nested_pattern = self
             .field_type
             .unwrap()
             .get_nested_pattern(false, &nested_ident);
         let index = self.index;
-        let lit_name = format!(
-            "{} field",
-            match index + 1 {
-                1 => String::from("1st"),
-                2 => String::from("2nd"),
-                3 => String::from("3rd"),
-                others => format!("{}th", others),
-            },
-        );
+        let lit_name = format!("{} field", ordinal(index + 1));
 
-        let nested_lit = quote::quote! { #nested_ident };
+        let nested_lit = quote::quote! { #nested_ident.to_lit() };
         let path = quote::quote! { String::from(#lit_name) };
         let reader = self.field_type.unwrap().get_lit_reader(
             &nested_ident,
@@ -238,57 +466,64 @@ impl ValuedField for UnnamedFiled {
             &nested_ident,
         );
 
+        let warning = self.get_warning_token_stream(&nested_ident);
         quote::quote! {
-            #nested_pattern if field_index == #index => {
-                #temp_var_name = Some(#reader?);
+            #nested_pattern if __annotation_index == #index => {
+                match #reader {
+                    Ok(value) => {
+                        #warning
+                        #temp_var_name = Some(value)
+                    },
+                    Err(e) => {
+                        __annotation_errors.push(e);
+                        __annotation_invalid_fields.insert(#temp_var_name_str);
+                    }
+                }
             }
         }
     }
 
+    fn is_required(&self) -> bool {
+        self.field_type.is_required()
+    }
+
+    fn has_default(&self) -> bool {
+        self.field_type.unwrap().has_default()
+    }
+
+    fn get_key_description(&self) -> String {
+        format!("the {} field", ordinal(self.index + 1))
+    }
+
     fn get_construct_token_stream(&self) -> TokenStream {
-        let temp_var_name = self.get_temp_var_name();
-        match self.field_type.is_required() {
-            true => quote::quote! {
-                match #temp_var_name {
-                    Some(value) => Ok(value),
-                    None => Err(syn::Error::new_spanned(
-                        quote::format_ident!{"Attribute"},
-                        "Not found value on required field"
-                    ))
-                }?
-            },
-            false => quote::quote! {
-                #temp_var_name
-            },
-        }
+        self.get_temp_var_name().into_token_stream()
     }
 }
 
 impl UnnamedFiled {
     pub fn from_ast(input: &SynField, index: usize) -> Result<Self, Error> {
-        let attribute = Self::get_attribute(&input.attrs)?;
+        let mut attribute = Self::get_attribute(&input.attrs)?;
 
-        let mut is_enum = false;
-        if attribute.enum_value.is_some() {
-            is_enum = attribute.enum_value.unwrap()
-        }
+        let is_enum = attribute.enum_value.unwrap_or(false);
         let field_type = FieldType::from_ast(&input.ty, is_enum)?;
-        let mut default: Option<DefaultValue> = None;
-        if attribute.default.is_some() {
-            default = Some(DefaultValue::from_string(
-                attribute.default.unwrap(),
+        let deprecated = attribute.deprecated.take();
+        let default = attribute.get_default(input, field_type.unwrap())?;
+        if let Some(DefaultValue::Field(_, _)) = default {
+            return Err(Error::new_spanned(
                 input,
-                &field_type.unwrap(),
-            )?)
+                "default_from_field can only be used on named fields",
+            ));
         }
         Ok(UnnamedFiled {
             index,
             default,
+            deprecated,
             field_type,
         })
     }
 }
 
+#[allow(clippy::enum_variant_names)]
 pub enum Fields {
     NamedFields(Vec<NamedField>),
     UnnamedField(Vec<UnnamedFiled>),
@@ -298,13 +533,13 @@ pub enum Fields {
 impl Fields {
     pub fn from_ast(fields: &SynFields) -> Result<Self, Error> {
         match fields {
-            SynFields::Named(named_fields) => Ok(Fields::NamedFields(
+            SynFields::Named(named_fields) => Ok(Fields::NamedFields(Self::sort_by_default(
                 named_fields
                     .named
                     .iter()
-                    .map(|field| NamedField::from_ast(field))
+                    .map(NamedField::from_ast)
                     .collect::<Result<Vec<NamedField>, Error>>()?,
-            )),
+            )?)),
             SynFields::Unnamed(unnamed_fields) => {
                 let mut fields = Vec::new();
 
@@ -318,6 +553,71 @@ impl Fields {
         }
     }
 
+    // Named fields are kept in the order their defaults have to be resolved in, so that a
+    // `default_from_field` is only read after the default of its source field is applied.
+    fn sort_by_default(fields: Vec<NamedField>) -> Result<Vec<NamedField>, Error> {
+        fn visit(
+            index: usize,
+            fields: &[NamedField],
+            states: &mut Vec<Option<bool>>,
+            stack: &mut Vec<usize>,
+            order: &mut Vec<usize>,
+        ) -> Result<(), Error> {
+            match states[index] {
+                Some(true) => return Ok(()),
+                Some(false) => {
+                    let cycle = stack[stack.iter().position(|item| *item == index).unwrap()..]
+                        .iter()
+                        .chain(std::iter::once(&index))
+                        .map(|item| fields[*item].name.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" -> ");
+                    return Err(Error::new(
+                        fields[index].name.span(),
+                        format!("Cycle detected in default_from_field: {}", cycle),
+                    ));
+                }
+                None => {}
+            };
+
+            states[index] = Some(false);
+            stack.push(index);
+            if let Some(source) = fields[index]
+                .default
+                .as_ref()
+                .and_then(|default| default.get_source_field())
+            {
+                let source_index = fields
+                    .iter()
+                    .position(|field| &field.name == source)
+                    .ok_or_else(|| {
+                        Error::new(
+                            fields[index].name.span(),
+                            format!("Unknown field in default_from_field: {}", source),
+                        )
+                    })?;
+                visit(source_index, fields, states, stack, order)?;
+            }
+            stack.pop();
+            states[index] = Some(true);
+            order.push(index);
+
+            Ok(())
+        }
+
+        let mut states = vec![None; fields.len()];
+        let mut order = Vec::with_capacity(fields.len());
+        for index in 0..fields.len() {
+            visit(index, &fields, &mut states, &mut Vec::new(), &mut order)?;
+        }
+
+        let mut fields: Vec<Option<NamedField>> = fields.into_iter().map(Some).collect();
+        Ok(order
+            .into_iter()
+            .map(|index| fields[index].take().unwrap())
+            .collect())
+    }
+
     pub fn parse_attributes_args_token_stream(
         &self,
         attributes_args_ident: Ident,
@@ -347,35 +647,106 @@ impl Fields {
             _ => Vec::new(),
         };
 
+        let resolve_default_token_stream: Vec<TokenStream> = match &self {
+            Fields::NamedFields(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_resolve_default_token_stream())
+                .collect(),
+            Fields::UnnamedField(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_resolve_default_token_stream())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let required_check_token_stream: Vec<TokenStream> = match &self {
+            Fields::NamedFields(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_required_check_token_stream())
+                .collect(),
+            Fields::UnnamedField(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_required_check_token_stream())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let unwrap_token_stream: Vec<TokenStream> = match &self {
+            Fields::NamedFields(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_unwrap_token_stream())
+                .collect(),
+            Fields::UnnamedField(fields) => fields
+                .iter()
+                .filter_map(|field| field.get_unwrap_token_stream())
+                .collect(),
+            _ => Vec::new(),
+        };
+
         let construct = self.construct_token_stream(&name);
+        let annotation_name = name.to_string();
+        let known_keys: Vec<TokenStream> = match &self {
+            Fields::NamedFields(fields) => fields
+                .iter()
+                .flat_map(|field| {
+                    let path = field.path.as_str();
+                    let field_name = field.name.to_string();
+                    let mut keys = vec![quote::quote! { (#path, #path) }];
+                    if field_name != path {
+                        keys.push(quote::quote! { (#field_name, #path) });
+                    }
+                    keys
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
 
         let index_value = match self {
-            Fields::UnnamedField(_) => quote::quote! {field_index},
+            Fields::UnnamedField(_) => quote::quote! {__annotation_index},
             _ => quote::quote! {_},
         };
 
         match &self {
             Fields::NamedFields(_) | Fields::UnnamedField(_) => {
                 quote::quote! {
+                    let mut __annotation_errors = annotation_rs::ErrorCollector::new();
+                    let mut __annotation_invalid_fields: std::collections::HashSet<&str> =
+                        std::collections::HashSet::new();
                     #(#temp_var_token_stream;)*
 
-                    for (#index_value, nested) in #attributes_args_ident.iter().enumerate() {
-                        match &nested {
+                    for (#index_value, __annotation_nested) in
+                        #attributes_args_ident.iter().enumerate()
+                    {
+                        match &__annotation_nested {
                             #(#parse_token_stream),*
                             _ => {
-                                return Err(syn::Error::new_spanned(
-                                    nested,
-                                    "Unexpected nested value in list"
+                                __annotation_errors.push(annotation_rs::unexpected_nested_error(
+                                    __annotation_nested,
+                                    &[#(#known_keys),*]
                                 ))
                             }
                         }
                     };
 
-                    #construct
+                    #(#resolve_default_token_stream)*
+
+                    let mut __annotation_missing_fields: Vec<&str> = Vec::new();
+                    #(#required_check_token_stream)*
+                    if !__annotation_missing_fields.is_empty() {
+                        __annotation_errors.push(annotation_rs::missing_fields_error(
+                            __annotation_span,
+                            #annotation_name,
+                            &__annotation_missing_fields
+                        ));
+                    }
+
+                    #(#unwrap_token_stream)*
+
+                    (Some(#construct), __annotation_errors.into_errors())
                 }
             }
-            _ => quote::quote! {
-                panic!("No Field attribute structure can not parse from attribute args");
+            Fields::None => quote::quote! {
+                (Some(#construct), Vec::new())
             },
         }
     }
@@ -388,9 +759,9 @@ impl Fields {
                     .map(|field| field.get_construct_token_stream())
                     .collect();
                 quote::quote! {
-                    Ok(#name {
+                    #name {
                         #(#fields_token_stream),*
-                    })
+                    }
                 }
             }
             Fields::UnnamedField(unnamed_field) => {
@@ -399,44 +770,13 @@ impl Fields {
                     .map(|field| field.get_construct_token_stream())
                     .collect();
                 quote::quote! {
-                    (
-                        Ok(#name (
-                            #(#fields_token_stream),*
-                        ))
+                    #name (
+                        #(#fields_token_stream),*
                     )
                 }
             }
             Fields::None => {
-                quote::quote! {Ok(#name)}
-            }
-        }
-    }
-
-    pub fn parse_meta_token_stream(&self, name: &Ident) -> TokenStream {
-        match self {
-            Fields::NamedFields(_) | Fields::UnnamedField(_) => {
-                quote::quote! {
-                    let input_meta_list = match input {
-                        syn::Meta::List(list) => Ok(list),
-                        _ => Err(syn::Error::new_spanned(
-                            &input,
-                            "Argument of attribute must be a List"
-                        ))
-                    }?;
-
-                    let mut attribute_args: syn::AttributeArgs = syn::AttributeArgs::new();
-                    for nested in input_meta_list.nested.clone() {
-                        attribute_args.push(nested)
-                    }
-
-                    Self::from_attribute_args(attribute_args)
-                }
-            }
-            Fields::None => {
-                let construct = self.construct_token_stream(&name);
-                quote::quote! {
-                    #construct
-                }
+                quote::quote! {#name}
             }
         }
     }
@@ -0,0 +1,272 @@
+use crate::attribute::AnnotationOptions;
+use crate::field::get_field_var_name;
+use crate::reader::{Interpolated, InterpolatedList};
+
+use helpers::{get_mod_path, get_nested_type, unwrap_punctuated_first, unwrap_type_path};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, Type};
+
+enum MemberKind {
+    /// `Option<T>`, repeated members are merged.
+    Single,
+    /// `Vec<T>`, every occurrence is kept.
+    Multiple,
+}
+
+/// A member annotation of a group, the field name is its key in the group attribute.
+struct Member {
+    ident: Ident,
+    field_ty: Type,
+    ty: Type,
+    kind: MemberKind,
+}
+
+impl Member {
+    pub fn from_ast(field: &Field) -> Result<Self, Error> {
+        let ident = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => return Err(Error::new_spanned(field, "Member of group must be named")),
+        };
+        let message = "Member of group must be `Option<T>` or `Vec<T>` of an annotation";
+        let type_path = unwrap_type_path(&field.ty, message)?;
+        let segment = unwrap_punctuated_first(
+            &type_path.path.segments,
+            Error::new_spanned(&field.ty, message),
+        )?;
+        let kind = match segment.ident.to_string().as_str() {
+            "Option" => MemberKind::Single,
+            "Vec" => MemberKind::Multiple,
+            _ => return Err(Error::new_spanned(&field.ty, message)),
+        };
+
+        Ok(Member {
+            ident,
+            field_ty: field.ty.clone(),
+            ty: get_nested_type(segment, message)?.clone(),
+            kind,
+        })
+    }
+
+    fn get_parse_arm(&self) -> TokenStream {
+        let ident = get_field_var_name(&self.ident);
+        let key = self.ident.to_string();
+        let ty = &self.ty;
+        let store = match self.kind {
+            MemberKind::Single => quote! {
+                match &mut #ident {
+                    Some(existing) => {
+                        if let Err(e) = annotation_rs::AnnotationStructure::merge(existing, value) {
+                            __annotation_errors.push(
+                                e.or_span(syn::spanned::Spanned::span(meta))
+                                    .with_path_prefix(#key)
+                            );
+                        }
+                    }
+                    None => #ident = Some(value),
+                }
+            },
+            MemberKind::Multiple => quote! {
+                #ident.push(value);
+            },
+        };
+
+        quote! {
+            annotation_rs::meta::NestedMeta::Meta(meta)
+                if meta.key() == annotation_rs::Symbol::new(#key) => {
+                let (value, value_errors) = match meta.nested() {
+                    Ok(__annotation_input) => {
+                        <#ty as annotation_rs::AnnotationStructure>::from_nested_meta_lenient(
+                            __annotation_input,
+                            __annotation_context,
+                            syn::spanned::Spanned::span(meta)
+                        )
+                    }
+                    Err(e) => (None, annotation_rs::Error::from_syn(e)),
+                };
+                __annotation_errors.push(annotation_rs::IntoErrors::into_errors_at(value_errors, #key));
+                if let Some(value) = value {
+                    #store
+                }
+            }
+        }
+    }
+
+    fn get_merge(&self) -> TokenStream {
+        let ident = &self.ident;
+        let key = ident.to_string();
+        match self.kind {
+            MemberKind::Single => quote! {
+                match (&mut self.#ident, other.#ident) {
+                    (Some(existing), Some(value)) => {
+                        annotation_rs::AnnotationStructure::merge(existing, value)
+                            .map_err(|e| e.with_path_prefix(#key))?;
+                    }
+                    (existing, value) => {
+                        if existing.is_none() {
+                            *existing = value;
+                        }
+                    }
+                }
+            },
+            MemberKind::Multiple => quote! {
+                self.#ident.extend(other.#ident);
+            },
+        }
+    }
+
+    fn get_to_token(&self) -> TokenStream {
+        let ident = &self.ident;
+        let value_interpolated = Interpolated::new("value");
+        let values_interpolated = InterpolatedList::new("values", Some(','));
+        match self.kind {
+            MemberKind::Single => quote! {
+                match &self.#ident {
+                    Some(value) => quote::quote!(Some(#value_interpolated)),
+                    None => quote::quote!(None),
+                }
+            },
+            MemberKind::Multiple => quote! {
+                {
+                    let values = &self.#ident;
+                    quote::quote!(vec![#values_interpolated])
+                }
+            },
+        }
+    }
+}
+
+/// A namespace attribute like `#[orm(table(name = "user"), index(columns("id")))]`, whose
+/// nested lists are parsed by the member annotations.
+pub struct AnnotationGroup {
+    ident: Ident,
+    path: String,
+    options: AnnotationOptions,
+    members: Vec<Member>,
+    mod_path: Option<TokenStream>,
+}
+
+impl AnnotationGroup {
+    pub fn from_ast(input: &DeriveInput) -> Result<Self, Error> {
+        let options = AnnotationOptions::from_attrs(&input.attrs)?;
+        if options.repeatable {
+            return Err(Error::new_spanned(
+                input,
+                "Group can't be repeatable, repeated groups are merged",
+            ));
+        }
+
+        match &input.data {
+            Data::Struct(data_struct) => match &data_struct.fields {
+                Fields::Named(fields) => Ok(AnnotationGroup {
+                    ident: input.ident.clone(),
+                    path: options
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| input.ident.to_string()),
+                    options,
+                    members: fields
+                        .named
+                        .iter()
+                        .map(Member::from_ast)
+                        .collect::<Result<Vec<_>, Error>>()?,
+                    mod_path: get_mod_path(&input.attrs)?,
+                }),
+                _ => Err(Error::new_spanned(input, "Group must have named fields")),
+            },
+            _ => Err(Error::new_spanned(input, "Group must be a struct")),
+        }
+    }
+
+    pub fn get_implement(&self) -> TokenStream {
+        let name = &self.ident;
+        let path = self.path.as_str();
+        let idents: Vec<&Ident> = self.members.iter().map(|member| &member.ident).collect();
+        let var_names: Vec<Ident> = idents
+            .iter()
+            .map(|ident| get_field_var_name(ident))
+            .collect();
+        let field_types: Vec<&Type> = self.members.iter().map(|member| &member.field_ty).collect();
+        let keys: Vec<String> = idents.iter().map(|ident| ident.to_string()).collect();
+        let parse_arms: Vec<TokenStream> = self
+            .members
+            .iter()
+            .map(|member| member.get_parse_arm())
+            .collect();
+        let merges: Vec<TokenStream> = self
+            .members
+            .iter()
+            .map(|member| member.get_merge())
+            .collect();
+        let to_tokens: Vec<TokenStream> = self
+            .members
+            .iter()
+            .map(|member| member.get_to_token())
+            .collect();
+        let options = self.options.get_implement();
+        let struct_path = match &self.mod_path {
+            Some(path) => quote! {
+                #path::#name
+            },
+            None => name.to_token_stream(),
+        };
+        let type_path = struct_path.to_string();
+
+        quote! {
+            impl annotation_rs::AnnotationStructure for #name {
+                fn get_path() -> annotation_rs::Symbol {
+                    annotation_rs::Symbol::new(#path)
+                }
+
+                const PATH: Option<&'static str> = Some(#path);
+
+                const TYPE_PATH: Option<&'static str> = Some(#type_path);
+
+                #options
+
+                fn from_nested_meta_lenient(
+                    __annotation_input: &[annotation_rs::meta::NestedMeta],
+                    __annotation_context: &annotation_rs::AnnotationContext,
+                    _span: proc_macro2::Span
+                ) -> (Option<Self>, Vec<annotation_rs::Error>)
+                where
+                    Self: std::marker::Sized {
+                    let mut __annotation_errors = annotation_rs::ErrorCollector::new();
+                    #(let mut #var_names: #field_types = Default::default();)*
+
+                    for __annotation_nested in __annotation_input.iter() {
+                        match __annotation_nested {
+                            #(#parse_arms,)*
+                            _ => {
+                                __annotation_errors.push(annotation_rs::unexpected_nested_error(
+                                    __annotation_nested,
+                                    &[#((#keys, #keys)),*]
+                                ))
+                            }
+                        }
+                    }
+
+                    (
+                        Some(#name { #(#idents: #var_names),* }),
+                        __annotation_errors.into_errors()
+                    )
+                }
+
+                fn merge(&mut self, other: Self) -> Result<(), annotation_rs::Error> {
+                    #(#merges)*
+
+                    Ok(())
+                }
+            }
+
+            impl quote::ToTokens for #name {
+                fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+                    #(let #var_names = #to_tokens;)*
+                    (quote::quote! {
+                        #struct_path { #(#idents: ##var_names),* }
+                    }).to_tokens(tokens);
+                }
+            }
+        }
+    }
+}
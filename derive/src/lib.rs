@@ -9,12 +9,20 @@ mod ty;
 mod attribute;
 use attribute::Annotation;
 
+mod attribute_macro;
+use attribute_macro::AttributeMacroConfig;
+
 mod enum_value;
+mod group;
 use crate::reader::{GetAnnotationParam, ReaderConfig};
 use enum_value::EnumValue;
+use group::AnnotationGroup;
 
 mod reader;
 
+mod target;
+use target::AnnotationTarget;
+
 #[proc_macro_derive(AnnotationEnumValue, attributes(variant_value, mod_path))]
 pub fn derive_enum_value(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -27,7 +35,7 @@ pub fn derive_enum_value(input: TokenStream) -> TokenStream {
     })
 }
 
-#[proc_macro_derive(Annotation, attributes(field, mod_path))]
+#[proc_macro_derive(Annotation, attributes(annotation, field, mod_path))]
 pub fn derive_attribute(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -39,18 +47,54 @@ pub fn derive_attribute(input: TokenStream) -> TokenStream {
     })
 }
 
+#[proc_macro_derive(AnnotationGroup, attributes(annotation, mod_path))]
+pub fn derive_annotation_group(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let group = AnnotationGroup::from_ast(&input);
+
+    TokenStream::from(match group {
+        Ok(value) => value.get_implement(),
+        Err(e) => e.to_compile_error(),
+    })
+}
+
+#[proc_macro_derive(AnnotationTarget, attributes(annotation))]
+pub fn derive_annotation_target(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let target = AnnotationTarget::from_ast(&input);
+
+    TokenStream::from(match target {
+        Ok(value) => value.get_implement(),
+        Err(e) => e.to_compile_error(),
+    })
+}
+
 #[proc_macro]
 pub fn generate_reader(input: TokenStream) -> TokenStream {
     let config = parse_macro_input!(input as ReaderConfig);
     TokenStream::from(config.get_reader())
 }
 
+#[proc_macro]
+pub fn generate_attribute(input: TokenStream) -> TokenStream {
+    let config = parse_macro_input!(input as AttributeMacroConfig);
+    TokenStream::from(config.get_attribute_macro())
+}
+
 #[proc_macro]
 pub fn __get_annotation(input: TokenStream) -> TokenStream {
     let config = parse_macro_input!(input as GetAnnotationParam);
     TokenStream::from(config.get_annotation())
 }
 
+#[proc_macro]
+pub fn __get_annotations(input: TokenStream) -> TokenStream {
+    let config = parse_macro_input!(input as GetAnnotationParam);
+    TokenStream::from(config.get_annotations())
+}
+
 #[proc_macro]
 pub fn __has_annotation(input: TokenStream) -> TokenStream {
     let config = parse_macro_input!(input as GetAnnotationParam);
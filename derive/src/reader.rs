@@ -5,7 +5,7 @@ use std::collections::HashSet;
 use syn::parse::{Parse, ParseBuffer};
 use syn::punctuated::Punctuated;
 use syn::token::{Bracket, Comma};
-use syn::{bracketed, Error, Token};
+use syn::{bracketed, Error, LitBool, Token};
 
 #[derive(Clone, Copy)]
 pub struct Interpolated<'a>(&'a str);
@@ -18,7 +18,7 @@ impl ToTokens for Interpolated<'_> {
 }
 
 impl Interpolated<'_> {
-    pub fn new(name: &str) -> Interpolated {
+    pub fn new(name: &str) -> Interpolated<'_> {
         Interpolated(name)
     }
 }
@@ -27,7 +27,7 @@ impl Interpolated<'_> {
 pub struct InterpolatedList<'a>(Interpolated<'a>, Option<char>);
 
 impl InterpolatedList<'_> {
-    pub fn new(name: &str, punctuate: Option<char>) -> InterpolatedList {
+    pub fn new(name: &str, punctuate: Option<char>) -> InterpolatedList<'_> {
         InterpolatedList(Interpolated(name), punctuate)
     }
 }
@@ -39,23 +39,82 @@ impl ToTokens for InterpolatedList<'_> {
             Delimiter::Parenthesis,
             self.0.into_token_stream(),
         ));
-        if self.1.is_some() {
-            tokens.append(Punct::new(self.1.unwrap(), Spacing::Alone));
+        if let Some(punctuate) = self.1 {
+            tokens.append(Punct::new(punctuate, Spacing::Alone));
         }
         tokens.append(Punct::new('*', Spacing::Alone));
     }
 }
 
-type AnnotationIdents = Punctuated<Ident, Token![,]>;
+/// An annotation of a reader, `Column` or `Column as column` when the annotation is renamed
+/// with `#[annotation(name = "column")]`, the ident after `as` is registered as helper attribute.
+pub struct AnnotationIdent {
+    pub ident: Ident,
+    pub helper: Option<Ident>,
+}
+
+impl AnnotationIdent {
+    fn helper(&self) -> &Ident {
+        self.helper.as_ref().unwrap_or(&self.ident)
+    }
+
+    // the helper is only known to the reader, the name of the annotation only to its crate, so
+    // they are compared in a constant of the generated crate
+    fn get_name_check(&self) -> TokenStream {
+        let ident = &self.ident;
+        let helper = self.helper();
+        let helper_string = helper.to_string();
+        let message = format!(
+            "`{}` is not the name of the annotation `{}`, use `{} as <name>` with the name of \
+             `#[annotation(name = ...)]`",
+            helper, ident, ident
+        );
+        quote::quote_spanned! { helper.span() =>
+            const _: () = assert!(
+                match <#ident as annotation_rs::AnnotationStructure>::PATH {
+                    Some(path) => annotation_rs::symbol::is_name_of(path, #helper_string),
+                    None => true,
+                },
+                #message
+            );
+        }
+    }
+}
+
+impl Parse for AnnotationIdent {
+    fn parse<'a>(input: &'a ParseBuffer<'a>) -> Result<Self, Error> {
+        let ident = input.parse()?;
+        let helper = match input.parse::<Option<Token![as]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        Ok(AnnotationIdent { ident, helper })
+    }
+}
+
+type AnnotationIdents = Punctuated<AnnotationIdent, Token![,]>;
 
 pub fn idents_to_vec(idents: &AnnotationIdents) -> Vec<Ident> {
-    idents.iter().cloned().collect()
+    idents.iter().map(|ident| ident.ident.clone()).collect()
+}
+
+/// `mode = derive` (the default) generates a derive macro, `mode = attribute` an attribute macro
+/// for functions, impl blocks and inline modules.
+#[derive(PartialEq)]
+pub enum ReaderMode {
+    Derive,
+    Attribute,
 }
 
 pub struct ReaderConfig {
     pub name: Ident,
     pub annotation_idents: AnnotationIdents,
     pub field_attr_idents: AnnotationIdents,
+    pub mode: ReaderMode,
+    /// `doc_comments = true` also reads annotations in doc comments, like `/// @Column`.
+    pub doc_comments: bool,
+    /// `cfg_attr = true` reads annotations in `cfg_attr` as if every predicate held.
+    pub cfg_attr: bool,
 }
 
 #[inline]
@@ -75,30 +134,68 @@ impl Parse for ReaderConfig {
     fn parse<'a>(input: &'a ParseBuffer<'a>) -> Result<Self, Error> {
         let name = input.parse()?;
         input.parse::<Comma>()?;
-        let annotation_idents = parse_punctuated_inside_bracket(&input)?;
-        let field_attr_idents = match input.parse::<Comma>() {
-            Ok(_) => parse_punctuated_inside_bracket(&input)?,
-            Err(_) => Punctuated::new(),
-        };
+        let annotation_idents = parse_punctuated_inside_bracket(input)?;
+        let mut field_attr_idents = Punctuated::new();
+        let mut mode = ReaderMode::Derive;
+        let mut doc_comments = false;
+        let mut cfg_attr = false;
+        if input.parse::<Option<Comma>>()?.is_some() && input.peek(Bracket) {
+            field_attr_idents = parse_punctuated_inside_bracket(input)?;
+            input.parse::<Option<Comma>>()?;
+        }
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "mode" => {
+                    let value: Ident = input.parse()?;
+                    mode = match value.to_string().as_str() {
+                        "derive" => ReaderMode::Derive,
+                        "attribute" => ReaderMode::Attribute,
+                        _ => {
+                            return Err(Error::new_spanned(
+                                value,
+                                "Unknown reader mode, expected `derive` or `attribute`",
+                            ))
+                        }
+                    };
+                }
+                "doc_comments" => doc_comments = input.parse::<LitBool>()?.value,
+                "cfg_attr" => cfg_attr = input.parse::<LitBool>()?.value,
+                _ => {
+                    return Err(Error::new_spanned(
+                        key,
+                        "Unknown option of reader, expected `mode`, `doc_comments` or `cfg_attr`",
+                    ))
+                }
+            }
+            input.parse::<Option<Comma>>()?;
+        }
         Ok(ReaderConfig {
             name,
             annotation_idents,
             field_attr_idents,
+            mode,
+            doc_comments,
+            cfg_attr,
         })
     }
 }
 
 impl ReaderConfig {
     fn get_annotation(&self) -> TokenStream {
+        if self.mode == ReaderMode::Attribute {
+            return quote::quote!(#[proc_macro_attribute]);
+        }
         let name = self.name.clone();
         let annotation_hash_set: HashSet<Ident> = [
             self.annotation_idents
                 .iter()
-                .cloned()
+                .map(|ident| ident.helper().clone())
                 .collect::<Vec<Ident>>(),
             self.field_attr_idents
                 .iter()
-                .cloned()
+                .map(|ident| ident.helper().clone())
                 .collect::<Vec<Ident>>(),
         ]
         .concat()
@@ -113,47 +210,130 @@ impl ReaderConfig {
         }
     }
 
-    fn annotation_reader_token_stream(annotation_map: Vec<Ident>) -> TokenStream {
+    fn annotation_reader_token_stream(
+        annotation_map: Vec<Ident>,
+        doc_comments: bool,
+    ) -> TokenStream {
         let structure_interpolated = Interpolated::new("structure");
         let fn_name_interpolated = Interpolated("fn_name");
+        let structures_interpolated = InterpolatedList::new("structures", Some(','));
 
+        let structure_vars: Vec<Ident> = annotation_map
+            .iter()
+            .map(|ident| format_ident!("{}_structure", ident.to_string().to_snake_case()))
+            .collect();
         let annotation_matches: Vec<TokenStream> = annotation_map
             .iter()
-            .map(|ident| {
+            .zip(structure_vars.iter())
+            .map(|(ident, structure_var)| {
+                quote::quote! {
+                    Ok(meta) if *path == #ident::get_path() => {
+                        let (structure, structure_errors) = match meta.nested() {
+                            Ok(nested) => {
+                                #ident::from_nested_meta_lenient(nested, context, syn::spanned::Spanned::span(meta))
+                            }
+                            Err(e) => (None, annotation_rs::Error::from_syn(e)),
+                        };
+                        errors.push(structure_errors);
+                        if let Some(structure) = structure {
+                            // repeated annotations are merged unless they are repeatable, like
+                            // the members of groups
+                            match #structure_var.first_mut() {
+                                Some(existing) if !#ident::is_repeatable() => {
+                                    if let Err(e) = existing.merge(structure) {
+                                        errors.push(e.or_span(*span));
+                                    }
+                                }
+                                _ => #structure_var.push(structure),
+                            }
+                        }
+                    }
+                }
+            }).collect();
+        let annotation_functions: Vec<TokenStream> = annotation_map
+            .iter()
+            .zip(structure_vars.iter())
+            .map(|(ident, structure_var)| {
                 let annotation_name = ident.to_string();
                 let snake_case_annotation_name = annotation_name.to_snake_case();
-                quote::quote! {
-                    Ok(meta) if attr.path == #ident::get_path() => {
-                        let fn_name = match &prefix {
+                let function_name = |fn_prefix: &str| {
+                    quote::quote! {
+                        match &prefix {
                             Some(prefix_name) => quote::format_ident!(
-                                "__attr_{}_{}",
+                                "{}_{}_{}",
+                                #fn_prefix,
                                 prefix_name.as_str().to_lowercase(),
                                 #snake_case_annotation_name
                             ),
                             None => quote::format_ident!(
-                                "__attr_{}",
+                                "{}_{}",
+                                #fn_prefix,
                                 #snake_case_annotation_name
                             )
-                        };
-                        match #ident::from_meta(&meta) {
-                            Ok(structure) => {
-                                annotation_map.insert(#annotation_name);
-                                Some(Ok(quote::quote! {
-                                    pub fn #fn_name_interpolated() -> #ident {#structure_interpolated}
-                                }))
-                            },
-                            Err(e) => Some(Err(e))
                         }
                     }
+                };
+                let fn_name = function_name("__attr");
+                let fns_name = function_name("__attrs");
+                quote::quote! {
+                    // the merged annotation, or the first occurrence of a repeatable one
+                    if let Some(structure) = #structure_var.first() {
+                        let fn_name = #fn_name;
+                        annotation_map.insert(#annotation_name);
+                        annotation_tokens.push(quote::quote! {
+                            pub fn #fn_name_interpolated() -> #ident {#structure_interpolated}
+                        });
+                    }
+                    // every occurrence of a repeatable annotation, the others hold at most the
+                    // merged one
+                    {
+                        let fn_name = #fns_name;
+                        let structures = &#structure_var;
+                        annotation_tokens.push(quote::quote! {
+                            pub fn #fn_name_interpolated() -> Vec<#ident> {
+                                vec![#structures_interpolated]
+                            }
+                        });
+                    }
                 }
-            }).collect();
+            })
+            .collect();
+        // only annotations with known paths are parsed, doc comments may contain lines like
+        // `@param id`
+        let doc_comments = match doc_comments {
+            true => quote::quote! {
+                attributes.extend(
+                    annotation_rs::DocAnnotation::from_attributes(&annotations)
+                        .into_iter()
+                        .filter(|doc| known_paths.iter().any(|path| doc.path == *path))
+                        .map(|doc| (doc.path.clone(), doc.span, doc.to_meta()))
+                );
+            },
+            false => TokenStream::new(),
+        };
         let count_interpolated = Interpolated::new("count");
         let annotations_interpolated = InterpolatedList::new("annotations", Some(','));
         let annotation_map_const_name_interpolated = Interpolated::new("annotation_map_const_name");
         quote::quote! {
-            |prefix: Option<String>, annotations: &Vec<syn::Attribute>| -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
+            |
+                prefix: Option<String>,
+                annotations: &[syn::Attribute],
+                context: &annotation_rs::AnnotationContext
+            | -> (Vec<proc_macro2::TokenStream>, Vec<annotation_rs::Error>) {
+                let mut errors = annotation_rs::ErrorCollector::new();
                 let mut annotation_map: std::collections::HashSet<&str> = std::collections::HashSet::new();
 
+                let known_paths: Vec<annotation_rs::Symbol> = vec![
+                    #(#annotation_map::get_path()),*
+                ];
+                let known_annotation_names: Vec<String> = vec![
+                    #(#annotation_map::get_path().to_string()),*
+                ];
+                let known_annotations: Vec<(&str, &str)> = known_annotation_names
+                    .iter()
+                    .map(|name| (name.as_str(), name.as_str()))
+                    .collect();
+
                 let annotation_map_const_name = match &prefix {
                     Some(prefix_name) => quote::format_ident!(
                         "{}_ATTRIBUTE_MAP",
@@ -164,134 +344,273 @@ impl ReaderConfig {
                     )
                 };
 
-                annotations.iter().map(
-                    |attr| -> Option<Result<proc_macro2::TokenStream, syn::Error>> {
-                        let meta = attr.parse_meta();
-
-                        match &meta {
-                            #(#annotation_matches,)*
-                            Err(e) => Some(Err(e.clone())),
-                            _ => None
+                // annotations are parsed leniently, so that their functions are still generated
+                // next to the errors
+                #(let mut #structure_vars: Vec<#annotation_map> = Vec::new();)*
+                // annotations in `cfg_attr` are only read when the context has a mode for them
+                let annotations: Vec<syn::Attribute> = match &context.cfg_attr {
+                    Some(mode) => match annotation_rs::unwrap_cfg_attrs(annotations, mode) {
+                        Ok(attrs) => attrs.into_iter().map(|attr| attr.attr).collect(),
+                        Err(e) => {
+                            errors.push(e);
+                            annotations.to_vec()
                         }
-                    }
-                )
-                .filter_map(|result| result)
-                .collect::<Result<Vec<proc_macro2::TokenStream>, syn::Error>>().map(
-                    |annotation_tokens| {
-                        let count = annotation_map.len();
-                        let annotations: Vec<_> = annotation_map.into_iter().collect();
-                        let tokens = vec![
-                            quote::quote!{
-                                const #annotation_map_const_name_interpolated: [&'static str; #count_interpolated] = [#annotations_interpolated];
+                    },
+                    None => annotations.to_vec(),
+                };
+                #[allow(unused_mut)]
+                let mut attributes: Vec<(
+                    syn::Path,
+                    proc_macro2::Span,
+                    syn::Result<annotation_rs::meta::Meta>
+                )> = annotations
+                    .iter()
+                    .map(|attr| (
+                        attr.path.clone(),
+                        syn::spanned::Spanned::span(attr),
+                        annotation_rs::meta::Meta::from_attribute(attr)
+                    ))
+                    .collect();
+                #doc_comments
+                for (path, span, meta) in attributes.iter() {
+                    match meta {
+                        #(#annotation_matches,)*
+                        // other attributes may belong to other crates, so a likely misspelling
+                        // is only a warning, and malformed ones are left to their owners
+                        Ok(_) => {
+                            if let Some(e) = annotation_rs::unknown_annotation_error(
+                                path,
+                                &known_annotations
+                            ) {
+                                context.warn(annotation_rs::Warning::new(e).with_span(*span));
                             }
-                        ];
+                        },
+                        Err(e) if known_paths.iter().any(|known| *path == *known) => {
+                            errors.push(e.clone())
+                        },
+                        Err(_) => {},
+                    }
+                }
+
+                let mut annotation_tokens: Vec<proc_macro2::TokenStream> = Vec::new();
+                #(#annotation_functions)*
 
-                        [tokens, annotation_tokens].concat()
+                let count = annotation_map.len();
+                let annotations: Vec<_> = annotation_map.into_iter().collect();
+                let tokens = vec![
+                    quote::quote!{
+                        pub const #annotation_map_const_name_interpolated: [&'static str; #count_interpolated] = [#annotations_interpolated];
                     }
-                )
+                ];
+
+                ([tokens, annotation_tokens].concat(), errors.into_errors())
             }
         }
     }
 
-    fn read_field_annotations_token_stream(annotation_map: Vec<Ident>) -> TokenStream {
-        let annotations_reader = Self::annotation_reader_token_stream(annotation_map);
+    fn read_field_annotations_token_stream(
+        annotation_map: Vec<Ident>,
+        doc_comments: bool,
+        members: TokenStream,
+    ) -> TokenStream {
+        let annotations_reader = Self::annotation_reader_token_stream(annotation_map, doc_comments);
         let tokens_interpolated = InterpolatedList::new("tokens", None);
         quote::quote! {
             let reader = #annotations_reader;
-            match input.data {
-                syn::Data::Struct(data_struct) => {
-                    data_struct.fields.iter().enumerate().map(
-                        |(index, field)| {
-                            let field_name = match &field.ident {
-                                Some(name) => name.to_string(),
-                                None => index.to_string()
-                            };
-
-                            let tokens = match reader(Some(field_name), &field.attrs)  {
-                                Ok(result) => result,
-                                Err(e) => return proc_macro2::TokenStream::from(e.to_compile_error())
-                            };
-
-                            quote::quote!{
-                                #tokens_interpolated
-                            }
-                        }
-                    ).collect::<Vec<proc_macro2::TokenStream>>()
-                },
-                syn::Data::Enum(data_enum) => {
-                    data_enum.variants.iter().map(
-                        |field| {
-                            let variant = field.ident.to_string();
-
-                            let tokens = match reader(Some(variant), &field.attrs) {
-                                Ok(result) => result,
-                                Err(e) => return proc_macro2::TokenStream::from(e.to_compile_error())
-                            };
-
-                            quote::quote!{
-                                #tokens_interpolated
-                            }
-                        }
-                    ).collect::<Vec<proc_macro2::TokenStream>>()
-                },
-                syn::Data::Union(data_union) => {
-                    data_union.fields.named.iter().map(
-                        |field| {
-                            let field_name = field.ident.as_ref().unwrap().clone().to_string();
-
-                            let tokens = match reader(Some(field_name), &field.attrs)  {
-                                Ok(result) => result,
-                                Err(e) => return proc_macro2::TokenStream::from(e.to_compile_error())
-                            };
-
-                            quote::quote!{
-                                #tokens_interpolated
-                            }
-                        }
-                    ).collect::<Vec<proc_macro2::TokenStream>>()
-                }
-            }
+            #members
+                .iter()
+                .map(|member| {
+                    let (tokens, member_errors) = reader(
+                        Some(member.name()),
+                        member.attrs,
+                        &member.context
+                    );
+                    errors.push(member_errors);
+
+                    quote::quote!{
+                        #tokens_interpolated
+                    }
+                })
+                .collect::<Vec<proc_macro2::TokenStream>>()
         }
     }
 
     pub fn get_reader(&self) -> TokenStream {
+        let reader = match self.mode {
+            ReaderMode::Derive => self.get_derive_reader(),
+            ReaderMode::Attribute => self.get_attribute_reader(),
+        };
+        let name_checks = self
+            .annotation_idents
+            .iter()
+            .chain(self.field_attr_idents.iter())
+            .map(AnnotationIdent::get_name_check);
+
+        quote::quote! {
+            #(#name_checks)*
+            #reader
+        }
+    }
+
+    fn get_cfg_attr(&self) -> TokenStream {
+        match self.cfg_attr {
+            true => quote::quote!(.with_cfg_attr(annotation_rs::CfgAttrMode::AssumeTrue)),
+            false => TokenStream::new(),
+        }
+    }
+
+    fn get_derive_reader(&self) -> TokenStream {
         let annotation = self.get_annotation();
+        let cfg_attr = self.get_cfg_attr();
         let fn_name = format_ident!("derive_{}", self.name.to_string().to_snake_case());
-        let struct_annotation_reader =
-            Self::annotation_reader_token_stream(idents_to_vec(&self.annotation_idents));
-        let field_annotation_reader =
-            Self::read_field_annotations_token_stream(idents_to_vec(&self.field_attr_idents));
+        let struct_annotation_reader = Self::annotation_reader_token_stream(
+            idents_to_vec(&self.annotation_idents),
+            self.doc_comments,
+        );
+        let field_annotation_reader = Self::read_field_annotations_token_stream(
+            idents_to_vec(&self.field_attr_idents),
+            self.doc_comments,
+            quote::quote!(annotation_rs::Member::from_derive_input_with_context(
+                &input, &context
+            )),
+        );
 
         let name_interpolated = Interpolated("name");
         let struct_annotation_tokens_interpolated =
             InterpolatedList::new("struct_annotation_tokens", None);
         let field_annotation_tokens_interpolated =
             InterpolatedList::new("field_annotation_tokens", None);
+        let warnings_interpolated = InterpolatedList::new("warnings", None);
+        let compile_errors_interpolated = Interpolated::new("compile_errors");
 
         quote::quote! {
             #annotation
             pub fn #fn_name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 use annotation_rs::AnnotationStructure;
                 let input = syn::parse_macro_input!(input as syn::DeriveInput);
+                let context = annotation_rs::AnnotationContext::from_derive_input(&input)#cfg_attr;
 
-                let name = input.ident;
+                let name = &input.ident;
 
                 let struct_annotation_reader = #struct_annotation_reader;
 
-                let struct_annotation_tokens: Vec<proc_macro2::TokenStream> = match struct_annotation_reader(None, &input.attrs) {
-                    Ok(tokens) => tokens,
-                    Err(e) => return proc_macro::TokenStream::from(e.to_compile_error())
-                };
+                let mut errors = annotation_rs::ErrorCollector::new();
+
+                let (struct_annotation_tokens, struct_annotation_errors) =
+                    struct_annotation_reader(None, &input.attrs, &context);
+                errors.push(struct_annotation_errors);
 
                 let field_annotation_tokens: Vec<proc_macro2::TokenStream> = {
                     #field_annotation_reader
                 };
 
+                let warnings = context.take_warnings();
+                let compile_errors = match errors.finish() {
+                    Ok(()) => proc_macro2::TokenStream::new(),
+                    Err(e) => e.to_compile_error(),
+                };
+
                 proc_macro::TokenStream::from(quote::quote! {
                     impl #name_interpolated {
                         #struct_annotation_tokens_interpolated
                         #field_annotation_tokens_interpolated
                     }
+
+                    #warnings_interpolated
+                    #compile_errors_interpolated
+                })
+            }
+        }
+    }
+
+    /// An attribute macro which reads the annotations of a function, impl block or inline
+    /// module and of its members, and removes them from the item.
+    fn get_attribute_reader(&self) -> TokenStream {
+        let annotation = self.get_annotation();
+        let cfg_attr = self.get_cfg_attr();
+        let fn_name = &self.name;
+        let struct_annotation_reader = Self::annotation_reader_token_stream(
+            idents_to_vec(&self.annotation_idents),
+            self.doc_comments,
+        );
+        let field_annotation_reader = Self::read_field_annotations_token_stream(
+            idents_to_vec(&self.field_attr_idents),
+            self.doc_comments,
+            quote::quote!(annotation_rs::Member::from_item_with_context(
+                &input, &context
+            )),
+        );
+        let annotation_idents = [
+            idents_to_vec(&self.annotation_idents),
+            idents_to_vec(&self.field_attr_idents),
+        ]
+        .concat();
+
+        let struct_annotation_tokens_interpolated =
+            InterpolatedList::new("struct_annotation_tokens", None);
+        let field_annotation_tokens_interpolated =
+            InterpolatedList::new("field_annotation_tokens", None);
+        let output_interpolated = Interpolated::new("output");
+        let warnings_interpolated = InterpolatedList::new("warnings", None);
+        let compile_errors_interpolated = Interpolated::new("compile_errors");
+
+        quote::quote! {
+            #annotation
+            pub fn #fn_name(
+                args: proc_macro::TokenStream,
+                input: proc_macro::TokenStream
+            ) -> proc_macro::TokenStream {
+                use annotation_rs::AnnotationStructure;
+                let args = proc_macro2::TokenStream::from(args);
+                let mut input = syn::parse_macro_input!(input as syn::Item);
+                let context = annotation_rs::AnnotationContext::new()
+                    .with_item(annotation_rs::ItemRef::Item(&input).ident())#cfg_attr;
+
+                let struct_annotation_reader = #struct_annotation_reader;
+
+                let mut errors = annotation_rs::ErrorCollector::new();
+                if !args.is_empty() {
+                    errors.push(syn::Error::new_spanned(args, "The reader takes no arguments"));
+                }
+
+                let (struct_annotation_tokens, struct_annotation_errors) = struct_annotation_reader(
+                    None,
+                    annotation_rs::ItemRef::Item(&input).attrs(),
+                    &context
+                );
+                errors.push(struct_annotation_errors);
+
+                let field_annotation_tokens: Vec<proc_macro2::TokenStream> = {
+                    #field_annotation_reader
+                };
+
+                let annotation_paths = [#(#annotation_idents::get_path()),*];
+                errors.handle(annotation_rs::check_unread_annotations(&input, &annotation_paths));
+                annotation_rs::remove_annotations(&mut input, &annotation_paths);
+                let output = match annotation_rs::expand_with_item(
+                    &input,
+                    quote::quote! {
+                        #struct_annotation_tokens_interpolated
+                        #field_annotation_tokens_interpolated
+                    }
+                ) {
+                    Ok(output) => output,
+                    Err(e) => {
+                        errors.push(e);
+                        quote::ToTokens::into_token_stream(input)
+                    }
+                };
+
+                let warnings = context.take_warnings();
+                let compile_errors = match errors.finish() {
+                    Ok(()) => proc_macro2::TokenStream::new(),
+                    Err(e) => e.to_compile_error(),
+                };
+
+                proc_macro::TokenStream::from(quote::quote! {
+                    #output_interpolated
+                    #warnings_interpolated
+                    #compile_errors_interpolated
                 })
             }
         }
@@ -322,10 +641,11 @@ impl Parse for GetAnnotationParam {
 }
 
 impl GetAnnotationParam {
-    pub fn get_annotation(&self) -> TokenStream {
+    fn fn_name(&self, fn_prefix: &str) -> Ident {
         let attr_str = self.annotation.to_string().to_snake_case();
-        let fn_name = format_ident!(
-            "__attr_{}",
+        format_ident!(
+            "{}_{}",
+            fn_prefix,
             match &self.property {
                 Some(prop) => {
                     let prop_str = prop.to_string().to_lowercase();
@@ -333,7 +653,11 @@ impl GetAnnotationParam {
                 }
                 None => attr_str,
             }
-        );
+        )
+    }
+
+    pub fn get_annotation(&self) -> TokenStream {
+        let fn_name = self.fn_name("__attr");
         let class = &self.class;
         let has_attr = self.has_annotation();
         quote::quote! {
@@ -344,6 +668,14 @@ impl GetAnnotationParam {
         }
     }
 
+    pub fn get_annotations(&self) -> TokenStream {
+        let fn_name = self.fn_name("__attrs");
+        let class = &self.class;
+        quote::quote! {
+            #class::#fn_name()
+        }
+    }
+
     pub fn has_annotation(&self) -> TokenStream {
         let const_name = format_ident!(
             "{}ATTRIBUTE_MAP",
@@ -352,7 +684,7 @@ impl GetAnnotationParam {
                     let prop_str = prop.to_string().to_uppercase();
                     format!("{}_", prop_str)
                 }
-                None => format!(""),
+                None => String::new(),
             }
         );
         let attr_str = self.annotation.to_string();
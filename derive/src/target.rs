@@ -0,0 +1,384 @@
+use crate::field::get_field_var_name;
+
+use helpers::{get_nested_type, Symbol};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, Type};
+
+/// How many values a receiver field takes, from its type.
+enum Cardinality {
+    One,
+    Optional,
+    Many,
+}
+
+impl Cardinality {
+    fn from_type(ty: &Type) -> Result<(Self, Type), Error> {
+        let segment = match ty {
+            Type::Path(type_path) => type_path.path.segments.last(),
+            _ => None,
+        };
+        match segment {
+            Some(segment) if segment.ident == "Option" || segment.ident == "Vec" => {
+                let nested_type = get_nested_type(segment, "Unexpected type path Argument")?;
+                Ok(match segment.ident == "Option" {
+                    true => (Cardinality::Optional, nested_type.clone()),
+                    false => (Cardinality::Many, nested_type.clone()),
+                })
+            }
+            _ => Ok((Cardinality::One, ty.clone())),
+        }
+    }
+}
+
+/// Fields which are filled from the derive input or member itself, by name.
+#[derive(PartialEq)]
+enum Source {
+    Ident,
+    Attrs,
+    Warnings,
+    Generics,
+    Vis,
+    Members,
+    Index,
+    Ty,
+}
+
+impl Source {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        Some(match ident.to_string().as_str() {
+            "ident" => Source::Ident,
+            "attrs" => Source::Attrs,
+            "warnings" => Source::Warnings,
+            "generics" => Source::Generics,
+            "vis" => Source::Vis,
+            "fields" | "variants" => Source::Members,
+            "index" => Source::Index,
+            "ty" => Source::Ty,
+            _ => return None,
+        })
+    }
+
+    fn is_item_only(&self) -> bool {
+        matches!(self, Source::Generics | Source::Vis | Source::Members)
+    }
+
+    fn is_member_only(&self) -> bool {
+        matches!(self, Source::Index | Source::Ty)
+    }
+}
+
+enum TargetFieldKind {
+    Annotation(Cardinality, Type),
+    Source(Source, Cardinality, Type),
+}
+
+struct TargetField {
+    ident: Ident,
+    kind: TargetFieldKind,
+}
+
+impl TargetField {
+    pub fn from_ast(field: &Field) -> Result<Self, Error> {
+        let ident = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => return Err(Error::new_spanned(field, "Field of target must be named")),
+        };
+        let (cardinality, ty) = Cardinality::from_type(&field.ty)?;
+        let kind = match field
+            .attrs
+            .iter()
+            .any(|attr| attr.path == Symbol::new("annotation"))
+        {
+            true => TargetFieldKind::Annotation(cardinality, ty),
+            false => match Source::from_ident(&ident) {
+                Some(source) => TargetFieldKind::Source(source, cardinality, ty),
+                None => {
+                    return Err(Error::new_spanned(
+                        &ident,
+                        "Unknown field of target, expected `#[annotation]` or one of \
+                         ident, attrs, warnings, generics, vis, fields, variants, index, ty",
+                    ))
+                }
+            },
+        };
+
+        Ok(TargetField { ident, kind })
+    }
+
+    fn get_annotation_token_stream(
+        cardinality: &Cardinality,
+        ty: &Type,
+        attrs: TokenStream,
+    ) -> TokenStream {
+        let structures = quote! {
+            annotation_rs::AnnotationStructures::<#ty>::from_attributes_with_context(
+                #attrs,
+                &__annotation_context
+            )
+            .map(|mut structures| {
+                __annotation_warnings.append(&mut structures.warnings);
+                structures
+            })
+        };
+        match cardinality {
+            Cardinality::One => quote! {
+                __annotation_errors.handle(#structures.and_then(|structures| structures.only_one()).and_then(
+                    |annotation| annotation.ok_or_else(|| syn::Error::new(
+                        __annotation_span,
+                        format!(
+                            "Missing annotation `{}`",
+                            <#ty as annotation_rs::AnnotationStructure>::get_path()
+                        )
+                    ))
+                ))
+            },
+            Cardinality::Optional => quote! {
+                __annotation_errors.handle(#structures.and_then(|structures| structures.only_one()))
+            },
+            Cardinality::Many => quote! {
+                __annotation_errors.handle(#structures.map(|structures| structures.into_attrs()))
+            },
+        }
+    }
+
+    fn get_item_token_stream(&self) -> TokenStream {
+        match &self.kind {
+            TargetFieldKind::Annotation(cardinality, ty) => Self::get_annotation_token_stream(
+                cardinality,
+                ty,
+                quote!(&__annotation_input.attrs),
+            ),
+            TargetFieldKind::Source(source, cardinality, ty) => match source {
+                Source::Ident | Source::Attrs | Source::Generics | Source::Vis => {
+                    let value = match source {
+                        Source::Ident => quote!(__annotation_input.ident.clone()),
+                        Source::Attrs => quote!(__annotation_input.attrs.clone()),
+                        Source::Generics => quote!(__annotation_input.generics.clone()),
+                        _ => quote!(__annotation_input.vis.clone()),
+                    };
+                    match cardinality {
+                        Cardinality::Optional => quote!(Some(Some(#value))),
+                        _ => quote!(Some(#value)),
+                    }
+                }
+                Source::Members => {
+                    let ty = match cardinality {
+                        Cardinality::Many => ty,
+                        _ => {
+                            return Error::new_spanned(&self.ident, "Members must be a `Vec`")
+                                .to_compile_error()
+                        }
+                    };
+                    quote! {
+                        __annotation_errors.handle(annotation_rs::ErrorCollector::collect::<_, Vec<_>, _, _>(
+                            annotation_rs::Member::from_derive_input_with_context(
+                                __annotation_input,
+                                &__annotation_context
+                            )
+                                .iter()
+                                .map(<#ty as annotation_rs::AnnotationTarget>::from_member)
+                        ))
+                    }
+                }
+                _ => TokenStream::new(),
+            },
+        }
+    }
+
+    fn get_member_token_stream(&self) -> TokenStream {
+        match &self.kind {
+            TargetFieldKind::Annotation(cardinality, ty) => Self::get_annotation_token_stream(
+                cardinality,
+                ty,
+                quote!(__annotation_member.attrs),
+            ),
+            TargetFieldKind::Source(source, cardinality, _) => {
+                let value = match source {
+                    Source::Ident => quote!(__annotation_member.ident.clone()),
+                    Source::Ty => quote!(__annotation_member.ty.cloned()),
+                    Source::Index => return quote!(Some(__annotation_member.index)),
+                    Source::Attrs => return quote!(Some(__annotation_member.attrs.to_vec())),
+                    _ => return TokenStream::new(),
+                };
+                let message = format!("The member has no {}", self.ident);
+                match cardinality {
+                    Cardinality::Optional => quote!(Some(#value)),
+                    _ => quote! {
+                        __annotation_errors.handle(#value.ok_or_else(|| syn::Error::new(__annotation_span, #message)))
+                    },
+                }
+            }
+        }
+    }
+
+    fn is_warnings(&self) -> bool {
+        matches!(&self.kind, TargetFieldKind::Source(Source::Warnings, _, _))
+    }
+
+    fn source(&self) -> Option<&Source> {
+        match &self.kind {
+            TargetFieldKind::Source(source, _, _) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A receiver struct, like `darling::FromDeriveInput`.
+pub struct AnnotationTarget {
+    ident: Ident,
+    fields: Vec<TargetField>,
+}
+
+impl AnnotationTarget {
+    pub fn from_ast(input: &DeriveInput) -> Result<Self, Error> {
+        match &input.data {
+            Data::Struct(data_struct) => match &data_struct.fields {
+                Fields::Named(fields) => {
+                    let fields = fields
+                        .named
+                        .iter()
+                        .map(TargetField::from_ast)
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    let item_only = fields
+                        .iter()
+                        .find(|field| field.source().is_some_and(Source::is_item_only));
+                    let member_only = fields
+                        .iter()
+                        .find(|field| field.source().is_some_and(Source::is_member_only));
+                    if let (Some(item_field), Some(member_field)) = (item_only, member_only) {
+                        return Err(Error::new(
+                            member_field.ident.span(),
+                            format!(
+                                "`{}` of a member can't be used together with `{}` of an item",
+                                member_field.ident, item_field.ident
+                            ),
+                        ));
+                    }
+
+                    Ok(AnnotationTarget {
+                        ident: input.ident.clone(),
+                        fields,
+                    })
+                }
+                _ => Err(Error::new_spanned(input, "Target must have named fields")),
+            },
+            _ => Err(Error::new_spanned(input, "Target must be a struct")),
+        }
+    }
+
+    fn get_constructor(
+        &self,
+        values: Vec<TokenStream>,
+        context: TokenStream,
+        span: TokenStream,
+    ) -> TokenStream {
+        let name = &self.ident;
+        let idents: Vec<&Ident> = self.fields.iter().map(|field| &field.ident).collect();
+        let var_names: Vec<Ident> = idents
+            .iter()
+            .map(|ident| get_field_var_name(ident))
+            .collect();
+        let warnings_fields: Vec<Ident> = self
+            .fields
+            .iter()
+            .filter(|field| field.is_warnings())
+            .map(|field| get_field_var_name(&field.ident))
+            .collect();
+        let values: Vec<TokenStream> = self
+            .fields
+            .iter()
+            .zip(values)
+            .filter(|(field, _)| !field.is_warnings())
+            .map(|(field, value)| {
+                let var_name = get_field_var_name(&field.ident);
+                quote!(let #var_name = #value;)
+            })
+            .collect();
+        // without a field for them, warnings are left in the context, which a member shares
+        // with the receiver of its parent
+        let keep_warnings = match warnings_fields.is_empty() {
+            true => quote! {
+                for warning in __annotation_warnings {
+                    __annotation_context.warn(warning);
+                }
+            },
+            false => quote!(#(let #warnings_fields = Some(__annotation_warnings);)*),
+        };
+
+        quote! {
+            #[allow(unused_variables)]
+            let __annotation_context = #context;
+            #[allow(unused_variables)]
+            let __annotation_span = #span;
+            let mut __annotation_errors = annotation_rs::ErrorCollector::new();
+            #[allow(unused_mut)]
+            let mut __annotation_warnings: Vec<annotation_rs::Warning> = Vec::new();
+            #(#values)*
+            __annotation_warnings.append(&mut __annotation_context.take_warnings());
+            #keep_warnings
+            __annotation_errors.finish()?;
+
+            Ok(#name {
+                #(#idents: #var_names.unwrap()),*
+            })
+        }
+    }
+
+    pub fn get_implement(&self) -> TokenStream {
+        let name = &self.ident;
+        let is_item = !self
+            .fields
+            .iter()
+            .any(|field| field.source().is_some_and(Source::is_member_only));
+        let is_member = !self
+            .fields
+            .iter()
+            .any(|field| field.source().is_some_and(Source::is_item_only));
+
+        let from_derive_input = match is_item {
+            true => {
+                let constructor = self.get_constructor(
+                    self.fields
+                        .iter()
+                        .map(|field| field.get_item_token_stream())
+                        .collect(),
+                    quote!(annotation_rs::AnnotationContext::from_derive_input(
+                        __annotation_input
+                    )),
+                    quote!(__annotation_input.ident.span()),
+                );
+                quote! {
+                    fn from_derive_input(__annotation_input: &syn::DeriveInput) -> Result<Self, syn::Error> {
+                        #constructor
+                    }
+                }
+            }
+            false => TokenStream::new(),
+        };
+        let from_member = match is_member {
+            true => {
+                let constructor = self.get_constructor(
+                    self.fields
+                        .iter()
+                        .map(|field| field.get_member_token_stream())
+                        .collect(),
+                    quote!(__annotation_member.context.clone()),
+                    quote!(__annotation_member.span()),
+                );
+                quote! {
+                    fn from_member(__annotation_member: &annotation_rs::Member) -> Result<Self, syn::Error> {
+                        #constructor
+                    }
+                }
+            }
+            false => TokenStream::new(),
+        };
+        quote! {
+            impl annotation_rs::AnnotationTarget for #name {
+                #from_derive_input
+                #from_member
+            }
+        }
+    }
+}
@@ -2,14 +2,17 @@ use super::reader::InterpolatedList;
 use proc_macro2::TokenStream;
 use quote::format_ident;
 use std::fmt;
-use syn::{Error, Field, Ident, PathSegment, Type as SynType, TypePath};
+use std::str::FromStr;
+use syn::{Error, Field, Ident, Lit, Path, PathSegment, Type as SynType, TypePath};
 
+use crate::field::get_field_var_name;
 use crate::reader::Interpolated;
-use helpers::{get_nested_type, get_nested_types, unwrap_punctuated_first, unwrap_type_path};
+use helpers::{get_nested_type, get_nested_types, unwrap_punctuated_first, unwrap_type_path, Case};
 
 pub enum Type {
     String,
     Bool,
+    Char,
     Integer(Ident),
     Float(Ident),
     Object(Ident),
@@ -20,7 +23,7 @@ pub enum Type {
 
 impl Type {
     fn get_nested_type_path(segment: &PathSegment) -> Result<Vec<&TypePath>, Error> {
-        get_nested_types(&segment, "Unexpect Arguments")?
+        get_nested_types(segment, "Unexpect Arguments")?
             .iter()
             .map(|&ty| unwrap_type_path(ty, "Argument of HashMap or Vec must be type path"))
             .collect()
@@ -37,12 +40,14 @@ impl Type {
         match token.to_string().as_str() {
             "String" => Ok(Type::String),
             "bool" => Ok(Type::Bool),
-            "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" => {
-                Ok(Type::Integer(token))
-            }
+            "char" => Ok(Type::Char),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "isize" | "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64"
+            | "NonZeroU128" | "NonZeroUsize" | "NonZeroI8" | "NonZeroI16" | "NonZeroI32"
+            | "NonZeroI64" | "NonZeroI128" | "NonZeroIsize" => Ok(Type::Integer(token)),
             "f32" | "f64" => Ok(Type::Float(token)),
             "Vec" => {
-                let nested_type_paths = Self::get_nested_type_path(&segment)?;
+                let nested_type_paths = Self::get_nested_type_path(segment)?;
 
                 match nested_type_paths.first() {
                     Some(&nested_type_path) => {
@@ -61,7 +66,7 @@ impl Type {
                 }
             }
             "HashMap" => {
-                let nested_type_paths = Self::get_nested_type_path(&segment)?;
+                let nested_type_paths = Self::get_nested_type_path(segment)?;
 
                 match nested_type_paths.first() {
                     Some(&key_type_path) => {
@@ -97,10 +102,21 @@ impl Type {
         }
     }
 
+    /// Whether the type is known to implement `Default`, which lenient parsing falls back to
+    /// for invalid or missing values.
+    pub fn has_default(&self) -> bool {
+        match self {
+            Type::Integer(ident) => !ident.to_string().starts_with("NonZero"),
+            Type::Object(_) | Type::Enum(_) => false,
+            _ => true,
+        }
+    }
+
     pub fn get_type_token_stream(&self) -> TokenStream {
         match self {
             Type::String => quote::quote! { String },
             Type::Bool => quote::quote! { bool },
+            Type::Char => quote::quote! { char },
             Type::Integer(ident) => quote::quote! { #ident },
             Type::Float(ident) => quote::quote! { #ident },
             Type::Object(ident) => quote::quote! { #ident },
@@ -119,56 +135,64 @@ impl Type {
     pub fn get_nested_pattern(&self, named: bool, nested_ident: &Ident) -> TokenStream {
         match (self, named) {
             (Type::String, true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::NameValue(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
                 )
             },
             (Type::String, false) => quote::quote! {
-                syn::NestedMeta::Lit(#nested_ident)
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
             },
             (Type::Bool, true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::NameValue(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
                 )
             },
             (Type::Bool, false) => quote::quote! {
-                syn::NestedMeta::Lit(#nested_ident)
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
+            },
+            (Type::Char, true) => quote::quote! {
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
+                )
+            },
+            (Type::Char, false) => quote::quote! {
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
             },
             (Type::Integer(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::NameValue(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
                 )
             },
             (Type::Integer(_), false) => quote::quote! {
-                syn::NestedMeta::Lit(#nested_ident)
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
             },
             (Type::Float(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::NameValue(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
                 )
             },
             (Type::Float(_), false) => quote::quote! {
-                syn::NestedMeta::Lit(#nested_ident)
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
             },
             (Type::Object(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(#nested_ident)
             },
             (Type::Enum(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::NameValue(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::NameValue(#nested_ident)
                 )
             },
             (Type::Enum(_), false) => quote::quote! {
-                syn::NestedMeta::Lit(#nested_ident)
+                annotation_rs::meta::NestedMeta::Value(#nested_ident)
             },
             (Type::List(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::List(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::List(#nested_ident)
                 )
             },
             (Type::Map(_), true) => quote::quote! {
-                syn::NestedMeta::Meta(
-                    syn::Meta::List(#nested_ident)
+                annotation_rs::meta::NestedMeta::Meta(
+                    annotation_rs::meta::Meta::List(#nested_ident)
                 )
             },
             _ => unreachable!(),
@@ -189,38 +213,59 @@ impl Type {
             Type::Bool => quote::quote! {
                 annotation_rs::get_lit_bool(&#nested_lit, &#path)
             },
-            Type::Integer(_) => {
+            Type::Char => quote::quote! {
+                annotation_rs::get_lit_char(&#nested_lit, &#path)
+            },
+            Type::Integer(ident) => {
                 let result_type = self.get_type_token_stream();
+                let type_name = ident.to_string();
                 quote::quote! {
-                    annotation_rs::get_lit_int::<#result_type, String>(&#nested_lit, &#path)
+                    annotation_rs::get_lit_int::<#result_type, String>(
+                        &#nested_lit,
+                        &#path,
+                        #type_name
+                    )
                 }
             }
-            Type::Float(_) => {
+            Type::Float(ident) => {
                 let result_type = self.get_type_token_stream();
+                let type_name = ident.to_string();
                 quote::quote! {
-                    annotation_rs::get_lit_float::<#result_type, String>(&#nested_lit, &#path)
+                    annotation_rs::get_lit_float::<#result_type, String>(
+                        &#nested_lit,
+                        &#path,
+                        #type_name
+                    )
                 }
             }
             Type::Object(_) => {
                 let result_type = self.get_type_token_stream();
                 quote::quote! {
-                    #result_type::from_meta(&#meta_list)
+                    #meta_list
+                        .nested()
+                        .map_err(annotation_rs::Error::from_syn)
+                        .and_then(|__annotation_input| #result_type::parse_nested_meta(
+                            __annotation_input,
+                            __annotation_context,
+                            syn::spanned::Spanned::span(#meta_list)
+                        ))
+                        .map_err(|errors| annotation_rs::IntoErrors::into_errors_at(errors, #path))
                 }
             }
             Type::Enum(_) => {
                 quote::quote! {
-                    annotation_rs::get_lit_str(&#nested_lit, &#path)?.parse().map_err(
-                        |e: annotation_rs::Error| {
-                            syn::Error::new_spanned(&#nested_lit, e.get_message())
-                        }
-                    )
+                    annotation_rs::get_lit_str(&#nested_lit, &#path).and_then(|value| {
+                        value.parse().map_err(|e: annotation_rs::Error| {
+                            e.with_span(syn::spanned::Spanned::span(&#nested_lit))
+                                .with_path_prefix(#path)
+                        })
+                    })
                 }
             }
             Type::List(ty) => {
                 let result_type = ty.get_type_token_stream();
-                let list_nested_ident = format_ident! {"list_{}", nested_ident};
-                let pattern = ty.get_nested_pattern(false, &list_nested_ident);
-                let list_nested_lit = quote::quote! {#list_nested_ident};
+                let list_nested_ident = format_ident! {"{}_list", nested_ident};
+                let list_nested_lit = quote::quote! { #list_nested_ident.to_lit() };
                 let reader = ty.get_lit_reader(
                     &list_nested_ident,
                     &list_nested_lit,
@@ -228,58 +273,58 @@ impl Type {
                     &list_nested_ident,
                 );
                 quote::quote! {
-                    #nested_ident.nested.iter().map(|meta_nested_meta| {
-                        match &meta_nested_meta {
-                            #pattern => #reader,
-                            _ => Err(syn::Error::new_spanned(
-                                &meta_nested_meta,
-                                "Only support List of Lit"
-                            ))
-                        }
-                    }).collect::<Result<Vec<#result_type>, syn::Error>>()
+                    annotation_rs::ErrorCollector::collect::<#result_type, Vec<#result_type>, _, _>(
+                        #nested_ident.nested.iter().map(|meta_nested_meta| {
+                            match meta_nested_meta.to_value() {
+                                Some(#list_nested_ident) => {
+                                    (#reader).map_err(annotation_rs::IntoErrors::into_errors)
+                                },
+                                None => Err(vec![
+                                    annotation_rs::Error::new("Only support List of Lit")
+                                        .with_span(syn::spanned::Spanned::span(meta_nested_meta))
+                                ])
+                            }
+                        })
+                    )
                 }
             }
             Type::Map(ty) => {
                 let result_type = ty.get_type_token_stream();
-                let map_nested_ident = format_ident! {"map_{}", nested_ident};
+                let map_nested_ident = format_ident! {"{}_map", nested_ident};
                 let pattern = ty.get_nested_pattern(true, &map_nested_ident);
-                let map_nested_lit = quote::quote! { #map_nested_ident.lit };
-                let reader =
-                    ty.get_lit_reader(&map_nested_ident, &map_nested_lit, path, &map_nested_ident);
+                let map_nested_lit = quote::quote! { #map_nested_ident.value.to_lit() };
+                let key_path = quote::quote! { map_key.clone() };
+                let reader = ty.get_lit_reader(
+                    &map_nested_ident,
+                    &map_nested_lit,
+                    &key_path,
+                    &map_nested_ident,
+                );
 
-                let path = match ty.as_ref() {
-                    Type::Object(_) => quote::quote! {&#map_nested_ident.path().segments},
-                    _ => quote::quote! {&#map_nested_ident.path.segments},
-                };
+                let key = ty.get_path_ident(map_nested_ident.clone());
                 quote::quote! {
-                    {
-                        let value_pairs: Result<Vec<(String, #result_type)>, syn::Error> =
+                    annotation_rs::ErrorCollector::collect::<
+                        (String, #result_type),
+                        std::collections::HashMap<String, #result_type>,
+                        _,
+                        _
+                    >(
                             #nested_ident.nested.iter().map(|meta_nested_meta| {
                                 match &meta_nested_meta {
                                     #pattern => {
-                                        Ok((
-                                             format!("{}", annotation_rs::unwrap_punctuated_first(
-                                                    #path,
-                                                    syn::Error::new_spanned(
-                                                        &#map_nested_ident,
-                                                        "Unexpected type path segment"
-                                                    )
-                                                 )?.ident
-                                             ),
-                                             #reader?
-                                        ))
+                                        let map_key = #key.to_string();
+                                        let value = (#reader).map_err(
+                                            |errors| annotation_rs::IntoErrors::into_errors_at(errors, #path)
+                                        )?;
+                                        Ok((map_key, value))
                                     },
-                                    _ => Err(syn::Error::new_spanned(
-                                        &meta_nested_meta,
-                                        "Only support List of Lit"
-                                    ))
+                                    _ => Err(vec![
+                                        annotation_rs::Error::new("Only support List of Lit")
+                                            .with_span(syn::spanned::Spanned::span(meta_nested_meta))
+                                    ])
                                 }
-                            }).collect();
-
-                        value_pairs.map(|pairs| {
-                            pairs.into_iter().collect::<std::collections::HashMap<String, #result_type>>()
-                        })
-                    }
+                            })
+                    )
                 }
             }
         }
@@ -287,13 +332,8 @@ impl Type {
 
     pub fn get_path_ident(&self, nested_ident: Ident) -> TokenStream {
         match self {
-            Type::Object(_) => quote::quote! {
-                (match &#nested_ident {
-                    syn::Meta::List(object_meta_list) => Ok(&object_meta_list.path),
-                    _ => Err(syn::Error::new_spanned(&#nested_ident, "Nested value must be List"))
-                }?)
-            },
-            _ => quote::quote! {#nested_ident.path},
+            Type::Object(_) => quote::quote! {#nested_ident.key()},
+            _ => quote::quote! {#nested_ident.key},
         }
     }
 
@@ -306,7 +346,7 @@ impl Type {
                     format_ident!("{}_nested", value_name),
                     false,
                 );
-                let temp_value_name = format_ident!("nested_value_tokens_{}", value_name);
+                let temp_value_name = format_ident!("{}_nested_value_tokens", value_name);
                 let temp_value_name_string = temp_value_name.to_string();
                 let nested_value_tokens_interpolated =
                     InterpolatedList::new(temp_value_name_string.as_str(), Some(','));
@@ -346,7 +386,7 @@ impl Type {
                 );
                 let key_interpolated = Interpolated::new("key");
 
-                let temp_value_name = format_ident!("nested_value_tokens_{}", value_name);
+                let temp_value_name = format_ident!("{}_nested_value_tokens", value_name);
                 let temp_value_name_string = temp_value_name.to_string();
                 let temp_value_name_interpolated =
                     InterpolatedList::new(temp_value_name_string.as_str(), Some(','));
@@ -392,8 +432,32 @@ impl Type {
                     }},
                 }
             }
+            Type::Integer(ident) if ident.to_string().starts_with("NonZero") => {
+                // `NonZero*` types do not implement `ToTokens`, rebuild them from their value
+                let temp_value = format_ident!("{}_temp_value", value_name);
+                let temp_value_string = temp_value.to_string();
+                let temp_value_interpolated = Interpolated::new(temp_value_string.as_str());
+
+                match is_option {
+                    true => quote::quote! {
+                        match &#value {
+                            Some(value) => {
+                                let #temp_value = value.get();
+                                quote::quote! {
+                                    Some(std::num::#ident::new(#temp_value_interpolated).unwrap())
+                                }
+                            },
+                            None => quote::quote!{None}
+                        }
+                    },
+                    false => quote::quote! {{
+                        let #temp_value = #value.get();
+                        quote::quote! {std::num::#ident::new(#temp_value_interpolated).unwrap()}
+                    }},
+                }
+            }
             Type::String => {
-                let temp_value = format_ident!("temp_value_{}", value_name);
+                let temp_value = format_ident!("{}_temp_value", value_name);
                 let temp_value_string = temp_value.to_string();
                 let temp_value_interpolated = Interpolated::new(temp_value_string.as_str());
                 let value_interpolated = Interpolated::new("value");
@@ -412,7 +476,7 @@ impl Type {
                 }
             }
             _ => {
-                let temp_value = format_ident!("temp_value_{}", value_name);
+                let temp_value = format_ident!("{}_temp_value", value_name);
                 let temp_value_string = temp_value.to_string();
                 let temp_value_interpolated = Interpolated::new(temp_value_string.as_str());
                 let value_interpolated = Interpolated::new("value");
@@ -439,6 +503,7 @@ impl fmt::Display for Type {
         match self {
             Type::String => write!(f, "String"),
             Type::Bool => write!(f, "bool"),
+            Type::Char => write!(f, "char"),
             Type::Integer(ty) => write!(f, "{}", ty),
             Type::Float(ty) => write!(f, "{}", ty),
             Type::Object(ty) => write!(f, "{}", ty),
@@ -501,42 +566,224 @@ impl FieldType {
     }
 }
 
+pub enum ContextSource {
+    ItemName,
+    FieldName,
+}
+
+impl ContextSource {
+    pub fn from_string(value: &str, field: &Field) -> Result<Self, Error> {
+        match value {
+            "item_name" => Ok(ContextSource::ItemName),
+            "field_name" => Ok(ContextSource::FieldName),
+            _ => Err(Error::new_spanned(
+                field,
+                "default_from must be \"item_name\" or \"field_name\"",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ContextSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextSource::ItemName => write!(f, "item_name"),
+            ContextSource::FieldName => write!(f, "field_name"),
+        }
+    }
+}
+
 pub enum DefaultValue {
     String(String),
     Bool(String),
+    Char(String),
     Integer(String),
     Float(String),
     Enum(String),
+    Context(ContextSource, Option<Case>),
+    Field(Ident, Option<Path>),
 }
 
 impl DefaultValue {
-    pub fn from_string(value: String, lit: &Field, ty: &Type) -> Result<Self, Error> {
+    /// Constant defaults are parsed when the annotation is read, so they are checked against
+    /// the field type here to report invalid values at the `default` literal.
+    pub fn from_string(value: String, lit: &Lit, field: &Field, ty: &Type) -> Result<Self, Error> {
+        check_constant(value.as_str(), ty).map_err(|message| {
+            Error::new_spanned(
+                lit,
+                format!("Invalid default value `{}` for {}: {}", value, ty, message),
+            )
+        })?;
+
         match ty {
             Type::String => Ok(DefaultValue::String(value)),
             Type::Bool => Ok(DefaultValue::Bool(value)),
+            Type::Char => Ok(DefaultValue::Char(value)),
             Type::Integer(_) => Ok(DefaultValue::Integer(value)),
             Type::Float(_) => Ok(DefaultValue::Float(value)),
             Type::Enum(_) => Ok(DefaultValue::Enum(value)),
             _ => Err(Error::new_spanned(
-                lit,
-                "Only support default value on String / Bool / Integer / Float / Enum",
+                field,
+                "Only support default value on String / Bool / Char / Integer / Float / Enum",
             )),
         }
     }
+
+    pub fn from_context(
+        source: String,
+        case: Option<String>,
+        field: &Field,
+        ty: &Type,
+    ) -> Result<Self, Error> {
+        let source = ContextSource::from_string(source.as_str(), field)?;
+        let case = match case {
+            Some(case) => Some(
+                Case::from_str(case.as_str())
+                    .map_err(|e| Error::new_spanned(field, e.get_message()))?,
+            ),
+            None => None,
+        };
+
+        match ty {
+            Type::String => Ok(DefaultValue::Context(source, case)),
+            _ => Err(Error::new_spanned(
+                field,
+                "Only support default_from on String",
+            )),
+        }
+    }
+
+    pub fn from_field(source: String, with: Option<String>, field: &Field) -> Result<Self, Error> {
+        let source = syn::parse_str::<Ident>(source.as_str()).map_err(|_| {
+            Error::new_spanned(field, "default_from_field must be the name of a field")
+        })?;
+        let with = match with {
+            Some(with) => Some(
+                syn::parse_str::<Path>(with.as_str())
+                    .map_err(|_| Error::new_spanned(field, "default_with must be a path"))?,
+            ),
+            None => None,
+        };
+
+        Ok(DefaultValue::Field(source, with))
+    }
+
+    pub fn get_source_field(&self) -> Option<&Ident> {
+        match self {
+            DefaultValue::Field(source, _) => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Constant defaults can be compared with a parsed value to detect redundant arguments.
+    pub fn is_constant(&self) -> bool {
+        matches!(
+            self,
+            DefaultValue::String(_)
+                | DefaultValue::Bool(_)
+                | DefaultValue::Char(_)
+                | DefaultValue::Integer(_)
+                | DefaultValue::Float(_)
+        )
+    }
+
+    pub fn get_token_stream(&self) -> TokenStream {
+        match self {
+            DefaultValue::Field(source, with) => {
+                let source = get_field_var_name(source);
+                match with {
+                    Some(with) => quote::quote! { #source.as_ref().map(|value| #with(value)) },
+                    None => quote::quote! { #source.clone() },
+                }
+            }
+            DefaultValue::Context(source, case) => {
+                let value = match source {
+                    ContextSource::ItemName => quote::quote! { __annotation_context.item_name() },
+                    ContextSource::FieldName => quote::quote! { __annotation_context.field_name() },
+                };
+
+                match case {
+                    Some(case) => quote::quote! {
+                        #value.map(|name| #case.convert(name.as_str()))
+                    },
+                    None => value,
+                }
+            }
+            _ => {
+                let value_string = self.to_string();
+                quote::quote! {Some(#value_string.parse().unwrap())}
+            }
+        }
+    }
 }
 
 impl fmt::Display for DefaultValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                DefaultValue::String(value) => value,
-                DefaultValue::Bool(value) => value,
-                DefaultValue::Integer(value) => value,
-                DefaultValue::Float(value) => value,
-                DefaultValue::Enum(value) => value,
+        match self {
+            DefaultValue::String(value)
+            | DefaultValue::Bool(value)
+            | DefaultValue::Char(value)
+            | DefaultValue::Integer(value)
+            | DefaultValue::Float(value)
+            | DefaultValue::Enum(value) => write!(f, "{}", value),
+            DefaultValue::Context(source, _) => write!(f, "{}", source),
+            DefaultValue::Field(source, _) => write!(f, "{}", source),
+        }
+    }
+}
+
+macro_rules! check_parse {
+    ($value: expr, $name: expr, $($ty: ty),*) => {
+        match $name {
+            $(stringify!($ty) => $value.parse::<$ty>().map(|_| ()).map_err(|e| e.to_string()),)*
+            _ => Ok(()),
+        }
+    };
+}
+
+fn check_constant(value: &str, ty: &Type) -> Result<(), String> {
+    use std::num::*;
+    match ty {
+        Type::Bool => value.parse::<bool>().map(|_| ()).map_err(|e| e.to_string()),
+        Type::Char => value.parse::<char>().map(|_| ()).map_err(|e| e.to_string()),
+        Type::Integer(ident) => check_parse!(
+            value,
+            ident.to_string().as_str(),
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            usize,
+            i8,
+            i16,
+            i32,
+            i64,
+            i128,
+            isize,
+            NonZeroU8,
+            NonZeroU16,
+            NonZeroU32,
+            NonZeroU64,
+            NonZeroU128,
+            NonZeroUsize,
+            NonZeroI8,
+            NonZeroI16,
+            NonZeroI32,
+            NonZeroI64,
+            NonZeroI128,
+            NonZeroIsize
+        ),
+        Type::Float(ident) => {
+            let infinite = match ident.to_string().as_str() {
+                "f32" => value.parse::<f32>().map(f32::is_infinite),
+                _ => value.parse::<f64>().map(f64::is_infinite),
+            };
+            match infinite.map_err(|e| e.to_string())? {
+                true => Err(String::from("number too large to fit in target type")),
+                false => Ok(()),
             }
-        )
+        }
+        _ => Ok(()),
     }
 }
@@ -0,0 +1,62 @@
+use crate::Error;
+use heck::{CamelCase, KebabCase, MixedCase, ShoutySnakeCase, SnakeCase};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Case {
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    ScreamingSnake,
+    Lower,
+    Upper,
+}
+
+impl Case {
+    pub fn convert(self, value: &str) -> String {
+        match self {
+            Case::Snake => value.to_snake_case(),
+            Case::Kebab => value.to_kebab_case(),
+            Case::Camel => value.to_mixed_case(),
+            Case::Pascal => value.to_camel_case(),
+            Case::ScreamingSnake => value.to_shouty_snake_case(),
+            Case::Lower => value.to_lowercase(),
+            Case::Upper => value.to_uppercase(),
+        }
+    }
+}
+
+impl FromStr for Case {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "snake" => Ok(Case::Snake),
+            "kebab" => Ok(Case::Kebab),
+            "camel" => Ok(Case::Camel),
+            "pascal" => Ok(Case::Pascal),
+            "screaming_snake" => Ok(Case::ScreamingSnake),
+            "lower" => Ok(Case::Lower),
+            "upper" => Ok(Case::Upper),
+            others => Err(Error::new(format!("Unexpected case: {}", others))),
+        }
+    }
+}
+
+impl ToTokens for Case {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Case::Snake => quote! { annotation_rs::Case::Snake },
+            Case::Kebab => quote! { annotation_rs::Case::Kebab },
+            Case::Camel => quote! { annotation_rs::Case::Camel },
+            Case::Pascal => quote! { annotation_rs::Case::Pascal },
+            Case::ScreamingSnake => quote! { annotation_rs::Case::ScreamingSnake },
+            Case::Lower => quote! { annotation_rs::Case::Lower },
+            Case::Upper => quote! { annotation_rs::Case::Upper },
+        }
+        .to_tokens(tokens)
+    }
+}
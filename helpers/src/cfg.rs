@@ -0,0 +1,221 @@
+use crate::ErrorCollector;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use std::collections::HashSet;
+use syn::parse::ParseStream;
+use syn::{Attribute, Error, Lit, Meta, NestedMeta, Path, Token};
+
+/// Enabled cfg options, like `unix` and `feature = "json"`, to evaluate `cfg_attr` predicates.
+#[derive(Clone, Default, Debug)]
+pub struct CfgSet {
+    options: HashSet<(String, Option<String>)>,
+}
+
+impl CfgSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable a name option like `unix` or `test`.
+    pub fn with(mut self, name: &str) -> Self {
+        self.options.insert((name.to_string(), None));
+        self
+    }
+
+    /// Enable a key-value option like `feature = "json"`.
+    pub fn with_value(mut self, name: &str, value: &str) -> Self {
+        self.options
+            .insert((name.to_string(), Some(value.to_string())));
+        self
+    }
+
+    pub fn contains(&self, name: &str, value: Option<&str>) -> bool {
+        self.options
+            .contains(&(name.to_string(), value.map(str::to_string)))
+    }
+
+    /// Evaluate a predicate like `all(unix, not(feature = "json"))`.
+    pub fn evaluate(&self, predicate: &Meta) -> Result<bool, Error> {
+        let name = |path: &Path| match path.get_ident() {
+            Some(ident) => Ok(ident.to_string()),
+            None => Err(Error::new_spanned(path, "cfg option must be an identifier")),
+        };
+        match predicate {
+            Meta::Path(path) => Ok(self.contains(&name(path)?, None)),
+            Meta::NameValue(name_value) => match &name_value.lit {
+                Lit::Str(value) => {
+                    Ok(self.contains(&name(&name_value.path)?, Some(&value.value())))
+                }
+                lit => Err(Error::new_spanned(
+                    lit,
+                    "Value of cfg option must be a string",
+                )),
+            },
+            Meta::List(list) => {
+                let nested = list
+                    .nested
+                    .iter()
+                    .map(|nested| match nested {
+                        NestedMeta::Meta(meta) => self.evaluate(meta),
+                        NestedMeta::Lit(lit) => {
+                            Err(Error::new_spanned(lit, "Expected a cfg predicate"))
+                        }
+                    })
+                    .collect::<Result<Vec<bool>, Error>>()?;
+                match name(&list.path)?.as_str() {
+                    "all" => Ok(nested.iter().all(|value| *value)),
+                    "any" => Ok(nested.iter().any(|value| *value)),
+                    "not" if nested.len() == 1 => Ok(!nested[0]),
+                    "not" => Err(Error::new_spanned(list, "`not` takes one cfg predicate")),
+                    _ => Err(Error::new_spanned(
+                        &list.path,
+                        "Expected `all`, `any` or `not`",
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// How the attributes inside `#[cfg_attr(predicate, ...)]` are read.
+#[derive(Clone, Debug)]
+pub enum CfgAttrMode {
+    /// Read the attributes whose predicate holds in the set.
+    Evaluate(CfgSet),
+    /// Read all attributes, as if every predicate held.
+    AssumeTrue,
+    /// Read all attributes and keep their predicates in `CfgAttribute::predicate`.
+    Expose,
+}
+
+/// An attribute unwrapped from `cfg_attr`, `predicate` is only kept in `CfgAttrMode::Expose`.
+/// The predicate of nested `cfg_attr`s is combined with `all(...)`.
+#[derive(Clone)]
+pub struct CfgAttribute {
+    pub attr: Attribute,
+    pub predicate: Option<Meta>,
+}
+
+impl From<&Attribute> for CfgAttribute {
+    fn from(attr: &Attribute) -> Self {
+        CfgAttribute {
+            attr: attr.clone(),
+            predicate: None,
+        }
+    }
+}
+
+pub fn is_cfg_attr(attr: &Attribute) -> bool {
+    attr.path.is_ident("cfg_attr")
+}
+
+/// Split `#[cfg_attr(predicate, a, b(...))]` into its predicate and the attributes `#[a]` and
+/// `#[b(...)]`.
+pub fn parse_cfg_attr(attr: &Attribute) -> Result<(Meta, Vec<Attribute>), Error> {
+    attr.parse_args_with(|input: ParseStream| {
+        let predicate: Meta = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let mut attrs = Vec::new();
+        while !input.is_empty() {
+            let path = input.call(Path::parse_mod_style)?;
+            let mut tokens = TokenStream::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                tokens.extend(std::iter::once(input.parse::<TokenTree>()?));
+            }
+            attrs.push(Attribute {
+                pound_token: attr.pound_token,
+                style: attr.style,
+                bracket_token: attr.bracket_token,
+                path,
+                tokens,
+            });
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok((predicate, attrs))
+    })
+}
+
+fn unwrap_cfg_attr(
+    attr: &Attribute,
+    outer: Option<&Meta>,
+    mode: &CfgAttrMode,
+) -> Result<Vec<CfgAttribute>, Error> {
+    if !is_cfg_attr(attr) {
+        return Ok(vec![CfgAttribute {
+            attr: attr.clone(),
+            predicate: outer.cloned(),
+        }]);
+    }
+
+    let (predicate, attrs) = parse_cfg_attr(attr)?;
+    if let CfgAttrMode::Evaluate(set) = mode {
+        if !set.evaluate(&predicate)? {
+            return Ok(Vec::new());
+        }
+    }
+    let predicate: Option<Meta> = match (mode, outer) {
+        (CfgAttrMode::Expose, Some(outer)) => Some(syn::parse_quote!(all(#outer, #predicate))),
+        (CfgAttrMode::Expose, None) => Some(predicate),
+        _ => None,
+    };
+
+    let mut unwrapped = Vec::new();
+    for attr in attrs.iter() {
+        unwrapped.extend(unwrap_cfg_attr(attr, predicate.as_ref(), mode)?);
+    }
+
+    Ok(unwrapped)
+}
+
+/// Replace the `cfg_attr`s of `attrs` by the attributes inside of them, see `CfgAttrMode`.
+/// Errors of all malformed `cfg_attr`s are reported together.
+pub fn unwrap_cfg_attrs(
+    attrs: &[Attribute],
+    mode: &CfgAttrMode,
+) -> Result<Vec<CfgAttribute>, Error> {
+    let mut errors = ErrorCollector::new();
+    let unwrapped = attrs
+        .iter()
+        .filter_map(|attr| errors.handle(unwrap_cfg_attr(attr, None, mode)))
+        .flatten()
+        .collect();
+
+    errors.finish_with(unwrapped)
+}
+
+/// Remove the attributes matching `remove` from `attr`, also inside of `cfg_attr`. A `cfg_attr`
+/// is dropped when no attribute remains in it, malformed ones are kept.
+pub fn strip_attribute<F: Fn(&Attribute) -> bool>(
+    attr: Attribute,
+    remove: &F,
+) -> Option<Attribute> {
+    if remove(&attr) {
+        return None;
+    }
+    if !is_cfg_attr(&attr) {
+        return Some(attr);
+    }
+
+    match parse_cfg_attr(&attr) {
+        Ok((predicate, attrs)) => {
+            let kept: Vec<TokenStream> = attrs
+                .into_iter()
+                .filter_map(|attr| strip_attribute(attr, remove))
+                .map(|attr| {
+                    let path = &attr.path;
+                    let tokens = &attr.tokens;
+                    quote!(#path #tokens)
+                })
+                .collect();
+            match kept.is_empty() {
+                true => None,
+                false => Some(Attribute {
+                    tokens: quote!((#predicate, #(#kept),*)),
+                    ..attr
+                }),
+            }
+        }
+        Err(_) => Some(attr),
+    }
+}
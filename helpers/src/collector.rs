@@ -0,0 +1,81 @@
+use crate::error::{to_syn_error, Error, IntoErrors};
+use std::iter::FromIterator;
+
+/// Accumulates errors, so that parsing can keep going after a recoverable error and report
+/// every diagnostic at once.
+#[derive(Default)]
+pub struct ErrorCollector {
+    errors: Vec<Error>,
+}
+
+impl ErrorCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<E: IntoErrors>(&mut self, errors: E) {
+        self.errors.extend(errors.into_errors())
+    }
+
+    pub fn handle<T, E: IntoErrors>(&mut self, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(errors) => {
+                self.push(errors);
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+
+    /// Combine all collected errors into one `syn::Error`.
+    pub fn finish(self) -> Result<(), syn::Error> {
+        match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(to_syn_error(self.errors)),
+        }
+    }
+
+    pub fn finish_with<T>(self, value: T) -> Result<T, syn::Error> {
+        self.finish().map(|_| value)
+    }
+
+    /// Collects an iterator of results like `Iterator::collect`, but keeps all errors instead
+    /// of stopping at the first one.
+    pub fn collect<T, C, E, I>(results: I) -> Result<C, Vec<Error>>
+    where
+        C: FromIterator<T>,
+        E: IntoErrors,
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        let mut collector = Self::new();
+        let values: C = results
+            .into_iter()
+            .filter_map(|result| collector.handle(result))
+            .collect();
+
+        match collector.is_empty() {
+            true => Ok(values),
+            false => Err(collector.into_errors()),
+        }
+    }
+}
+
+impl<E: IntoErrors> Extend<E> for ErrorCollector {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, errors: I) {
+        for error in errors {
+            self.push(error);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::meta::Meta;
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use syn::spanned::Spanned;
+use syn::{Attribute, Lit, Path};
+
+/// An annotation in a doc comment, like `/// @Route("/x", method = "GET")`.
+///
+/// The annotation starts a line of the comment, its path is followed by `(` or the end of the
+/// line, and it continues on the following lines until its parentheses are closed. Lines in
+/// fenced code blocks are skipped, as is prose like `@Table is deprecated`. Doc comments are
+/// not checked by the compiler, so the tokens of the annotation have the span of the doc
+/// attribute it starts in.
+#[derive(Clone)]
+pub struct DocAnnotation {
+    pub path: Path,
+    pub span: Span,
+    text: String,
+}
+
+impl DocAnnotation {
+    /// Scan the `#[doc = "..."]` attributes for annotations. Lines like `@param id` are returned
+    /// as well, compare the path before parsing them with `to_meta()`.
+    pub fn from_attributes(attrs: &[Attribute]) -> Vec<Self> {
+        let lines: Vec<(String, Span)> = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(name_value)) => match name_value.lit {
+                    Lit::Str(doc) => Some((doc.value(), attr.span())),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .flat_map(|(doc, span)| {
+                doc.lines()
+                    .map(|line| (line.to_string(), span))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut annotations = Vec::new();
+        let mut in_code_block = false;
+        let mut lines = lines.into_iter();
+        while let Some((line, span)) = lines.next() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+            let text = match trimmed.strip_prefix('@') {
+                Some(text) => text.to_string(),
+                None => continue,
+            };
+            let path_len = text
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .unwrap_or(text.len());
+            let rest = &text[path_len..];
+            if !(rest.starts_with('(') || rest.trim_end().is_empty()) {
+                continue;
+            }
+            let path = match syn::parse_str::<Path>(&text[..path_len]) {
+                Ok(path) => respan_path(path, span),
+                Err(_) => continue,
+            };
+
+            let mut text = text;
+            while unclosed_parens(&text) > 0 {
+                match lines.next() {
+                    Some((next_line, _)) => {
+                        text.push('\n');
+                        text.push_str(&next_line);
+                    }
+                    None => break,
+                }
+            }
+
+            annotations.push(DocAnnotation { path, span, text });
+        }
+
+        annotations
+    }
+
+    /// Parse the annotation with the grammar of `annotation_rs::meta`.
+    pub fn to_meta(&self) -> syn::Result<Meta> {
+        let tokens: TokenStream = self.text.parse().map_err(|_| {
+            syn::Error::new(self.span, "Invalid tokens in annotation of doc comment")
+        })?;
+        syn::parse2(respan(tokens, self.span))
+    }
+}
+
+/// Parentheses opened but not closed yet, parentheses in string literals are skipped.
+fn unclosed_parens(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        match (in_string, escaped, c) {
+            (true, true, _) => escaped = false,
+            (true, false, '\\') => escaped = true,
+            (_, false, '"') => in_string = !in_string,
+            (false, _, '(') => depth += 1,
+            (false, _, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth
+}
+
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::Group(respanned)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
+}
+
+fn respan_path(path: Path, span: Span) -> Path {
+    syn::parse2(respan(quote::ToTokens::into_token_stream(path), span)).unwrap()
+}
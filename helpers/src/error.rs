@@ -1,29 +1,374 @@
+use crate::with_suggestion;
+use proc_macro2::Span;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// Required keys of an annotation are not present, `keys` are in declaration order.
+    MissingField {
+        annotation: String,
+        keys: Vec<String>,
+    },
+    /// A value has a different type than the field it is assigned to.
+    TypeMismatch {
+        expected: String,
+    },
+    /// A number which is larger than the maximum of its type.
+    Overflow {
+        value: String,
+        ty: String,
+    },
+    /// A number which is smaller than the minimum of its type.
+    Underflow {
+        value: String,
+        ty: String,
+    },
+    /// A literal with a suffix of another type, like `1u8` for an `u16` field.
+    SuffixMismatch {
+        expected: String,
+        found: String,
+    },
+    /// A key which no field of the annotation accepts.
+    UnknownKey {
+        key: String,
+        suggestion: Option<String>,
+    },
+    /// An attribute which looks like a misspelled annotation.
+    UnknownAnnotation {
+        name: String,
+        suggestion: String,
+    },
+    InvalidEnumValue {
+        enum_name: String,
+        value: String,
+        suggestion: Option<String>,
+    },
+    /// A value of the right type which the field rejects, like `0` for `NonZeroU32`. Use
+    /// `Error::validation` to report checks of your own.
+    Validation(String),
+    /// An error reported by `syn`, which is kept as the `source()` of the error.
+    Syntax(String),
+    Custom(String),
+}
+
+/// A structured annotation error.
+///
+/// `path` is the location of the failure inside the annotation, like `["object", "enum2"]`
+/// for `#[Full(object(enum2 = "..."))]`. Errors about a whole annotation (missing or unknown
+/// keys) carry the path of that annotation, which is empty for the outermost one.
 #[derive(Debug, Clone)]
 pub struct Error {
-    message: String,
+    kind: ErrorKind,
+    span: Option<Span>,
+    path: Vec<String>,
+    // boxed twice to keep the pointer thin, `Error` is returned by value everywhere
+    source: Option<Arc<Box<dyn StdError + Send + Sync>>>,
 }
+
 impl Error {
     pub fn new<T: Display>(message: T) -> Self {
+        Self::from_kind(ErrorKind::Custom(message.to_string()))
+    }
+
+    pub fn from_kind(kind: ErrorKind) -> Self {
         Error {
-            message: message.to_string(),
+            kind,
+            span: None,
+            path: Vec::new(),
+            source: None,
         }
     }
+
+    pub fn missing_field(annotation: &str, keys: &[&str]) -> Self {
+        Self::from_kind(ErrorKind::MissingField {
+            annotation: annotation.to_string(),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+        })
+    }
+
+    pub fn type_mismatch(expected: &str) -> Self {
+        Self::from_kind(ErrorKind::TypeMismatch {
+            expected: expected.to_string(),
+        })
+    }
+
+    pub fn unknown_key(key: &str, suggestion: Option<&str>) -> Self {
+        Self::from_kind(ErrorKind::UnknownKey {
+            key: key.to_string(),
+            suggestion: suggestion.map(|suggestion| suggestion.to_string()),
+        })
+    }
+
+    pub fn invalid_enum_value(enum_name: &str, value: &str, suggestion: Option<&str>) -> Self {
+        Self::from_kind(ErrorKind::InvalidEnumValue {
+            enum_name: enum_name.to_string(),
+            value: value.to_string(),
+            suggestion: suggestion.map(|suggestion| suggestion.to_string()),
+        })
+    }
+
+    pub fn validation<T: Display>(message: T) -> Self {
+        Self::from_kind(ErrorKind::Validation(message.to_string()))
+    }
+
+    /// Split a (possibly combined) `syn::Error` into structured errors.
+    pub fn from_syn(error: syn::Error) -> Vec<Self> {
+        error
+            .into_iter()
+            .map(|error| Error {
+                kind: ErrorKind::Syntax(error.to_string()),
+                span: Some(error.span()),
+                path: Vec::new(),
+                source: Some(Arc::new(Box::new(error))),
+            })
+            .collect()
+    }
+
+    /// Split a (possibly combined) syn 2 error into structured errors.
+    #[cfg(feature = "syn2")]
+    pub fn from_syn2(error: syn2::Error) -> Vec<Self> {
+        error
+            .into_iter()
+            .map(|error| Error {
+                kind: ErrorKind::Syntax(error.to_string()),
+                span: Some(error.span()),
+                path: Vec::new(),
+                source: Some(Arc::new(Box::new(error))),
+            })
+            .collect()
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Set the span if the error does not have one yet.
+    pub fn or_span(mut self, span: Span) -> Self {
+        self.span = self.span.or(Some(span));
+        self
+    }
+
+    pub fn with_path_prefix<T: Display>(mut self, segment: T) -> Self {
+        self.path.insert(0, segment.to_string());
+        self
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn path_string(&self) -> String {
+        self.path.join(".")
+    }
+
     pub fn get_message(&self) -> String {
-        self.message.clone()
+        self.to_string()
+    }
+
+    pub fn to_syn_error(&self) -> syn::Error {
+        syn::Error::new(self.span.unwrap_or_else(Span::call_site), self.to_string())
+    }
+
+    #[cfg(feature = "syn2")]
+    pub fn to_syn2_error(&self) -> syn2::Error {
+        syn2::Error::new(self.span.unwrap_or_else(Span::call_site), self.to_string())
+    }
+
+    fn location(&self, preposition: &str) -> String {
+        match self.path.is_empty() {
+            true => String::new(),
+            false => format!(" {} `{}`", preposition, self.path_string()),
+        }
     }
 }
 
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
-        None
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref().as_ref() as &(dyn StdError + 'static))
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", self.message)
+        match &self.kind {
+            ErrorKind::MissingField { annotation, keys } => {
+                write!(
+                    f,
+                    "Missing required key {} in annotation `{}`{}",
+                    keys.first().map(String::as_str).unwrap_or_default(),
+                    annotation,
+                    self.location("at")
+                )?;
+                match keys.len() > 1 {
+                    true => write!(f, ", other missing required keys: {}", keys[1..].join(", ")),
+                    false => Ok(()),
+                }
+            }
+            ErrorKind::TypeMismatch { expected } => match self.path.is_empty() {
+                true => write!(f, "expected lit to be a {}", expected),
+                false => write!(
+                    f,
+                    "expected {} lit to be a {}",
+                    self.path_string(),
+                    expected
+                ),
+            },
+            ErrorKind::Overflow { value, ty } => write!(
+                f,
+                "{} is too large for {}{}",
+                value,
+                ty,
+                self.location("at")
+            ),
+            ErrorKind::Underflow { value, ty } => write!(
+                f,
+                "{} is too small for {}{}",
+                value,
+                ty,
+                self.location("at")
+            ),
+            ErrorKind::SuffixMismatch { expected, found } => write!(
+                f,
+                "literal suffix `{}` does not match type {}{}",
+                found,
+                expected,
+                self.location("at")
+            ),
+            ErrorKind::UnknownKey { key, suggestion } => write!(
+                f,
+                "{}",
+                with_suggestion(
+                    format!("Unexpected key `{}`{}", key, self.location("in")),
+                    suggestion.as_deref()
+                )
+            ),
+            ErrorKind::UnknownAnnotation { name, suggestion } => write!(
+                f,
+                "{}",
+                with_suggestion(
+                    format!("Unknown annotation `{}`", name),
+                    Some(suggestion.as_str())
+                )
+            ),
+            ErrorKind::InvalidEnumValue {
+                enum_name,
+                value,
+                suggestion,
+            } => write!(
+                f,
+                "{}",
+                with_suggestion(
+                    format!(
+                        "Unexpected {} value: {}{}",
+                        enum_name,
+                        value,
+                        self.location("for")
+                    ),
+                    suggestion.as_deref()
+                )
+            ),
+            ErrorKind::Validation(message)
+            | ErrorKind::Syntax(message)
+            | ErrorKind::Custom(message) => match self.path.is_empty() {
+                true => write!(f, "{}", message),
+                false => write!(f, "`{}`: {}", self.path_string(), message),
+            },
+        }
+    }
+}
+
+impl From<Error> for syn::Error {
+    fn from(error: Error) -> Self {
+        error.to_syn_error()
+    }
+}
+
+/// Conversion into a list of structured errors, implemented for everything the generated
+/// parsers have to report.
+pub trait IntoErrors {
+    fn into_errors(self) -> Vec<Error>;
+
+    /// Convert into errors located under `segment`, used when a nested value is parsed.
+    fn into_errors_at<T: Display>(self, segment: T) -> Vec<Error>
+    where
+        Self: Sized,
+    {
+        let segment = segment.to_string();
+        self.into_errors()
+            .into_iter()
+            .map(|error| error.with_path_prefix(&segment))
+            .collect()
+    }
+}
+
+impl IntoErrors for Error {
+    fn into_errors(self) -> Vec<Error> {
+        vec![self]
+    }
+}
+
+impl IntoErrors for Vec<Error> {
+    fn into_errors(self) -> Vec<Error> {
+        self
+    }
+}
+
+impl IntoErrors for syn::Error {
+    fn into_errors(self) -> Vec<Error> {
+        Error::from_syn(self)
+    }
+}
+
+#[cfg(feature = "syn2")]
+impl From<Error> for syn2::Error {
+    fn from(error: Error) -> Self {
+        error.to_syn2_error()
+    }
+}
+
+#[cfg(feature = "syn2")]
+impl IntoErrors for syn2::Error {
+    fn into_errors(self) -> Vec<Error> {
+        Error::from_syn2(self)
     }
 }
+
+/// Combine structured errors into one `syn2::Error`, keeping the span and message of each.
+#[cfg(feature = "syn2")]
+pub fn to_syn2_error<E: IntoErrors>(errors: E) -> syn2::Error {
+    let mut errors = errors.into_errors().into_iter().map(syn2::Error::from);
+    let mut combined = errors
+        .next()
+        .unwrap_or_else(|| syn2::Error::new(Span::call_site(), "Unknown error"));
+    for error in errors {
+        combined.combine(error);
+    }
+
+    combined
+}
+
+/// Combine structured errors into one `syn::Error`, keeping the span and message of each.
+pub fn to_syn_error<E: IntoErrors>(errors: E) -> syn::Error {
+    let mut errors = errors.into_errors().into_iter().map(syn::Error::from);
+    let mut combined = errors
+        .next()
+        .unwrap_or_else(|| syn::Error::new(Span::call_site(), "Unknown error"));
+    for error in errors {
+        combined.combine(error);
+    }
+
+    combined
+}
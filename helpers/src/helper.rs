@@ -1,8 +1,12 @@
-use crate::Symbol;
-use proc_macro2::TokenStream;
+use crate::error::{Error as AnnotationError, ErrorKind};
+use crate::meta::NestedMeta;
+use crate::{suggest, Symbol};
+use proc_macro2::{Span, TokenStream};
 use std::fmt::Display;
+use std::num::{IntErrorKind, ParseFloatError, ParseIntError};
 use std::str::FromStr;
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{
     Attribute, Error, GenericArgument, Lit, Meta, PathArguments, PathSegment, Type, TypePath,
 };
@@ -62,69 +66,218 @@ pub fn unwrap_type_path<'a>(ty: &'a Type, message: &'static str) -> Result<&'a T
 }
 
 #[inline]
-pub fn get_lit_str<U: Display>(lit: &Lit, ident: &U) -> Result<String, Error> {
+pub fn get_lit_str<U: Display>(lit: &Lit, ident: &U) -> Result<String, AnnotationError> {
     match lit {
         Lit::Str(lit_str) => Ok(lit_str.value()),
-        _ => Err(Error::new_spanned(
-            lit,
-            format!("expected {} lit to be a string", ident),
-        )),
+        _ => Err(type_mismatch(lit, ident, "string")),
     }
 }
 
 #[inline]
-pub fn get_lit_as_string<U: Display>(lit: &Lit, ident: &U) -> Result<String, Error> {
+pub fn get_lit_as_string<U: Display>(lit: &Lit, ident: &U) -> Result<String, AnnotationError> {
     match lit {
         Lit::Str(lit_str) => Ok(lit_str.value()),
         Lit::Int(lit_int) => Ok(lit_int.to_string()),
         Lit::Float(lit_float) => Ok(lit_float.to_string()),
         Lit::Bool(lit_bool) => Ok(lit_bool.value.to_string()),
-        _ => Err(Error::new_spanned(
-            lit,
-            format!("expected {} lit to be a string/integer/float/boll", ident),
-        )),
+        Lit::Char(lit_char) => Ok(lit_char.value().to_string()),
+        _ => Err(type_mismatch(lit, ident, "string/integer/float/bool/char")),
     }
 }
 
+/// Parse an integer literal into `T`, which is any primitive integer or `NonZero*` type named
+/// `type_name`. Values out of the range of `T` and literals with a suffix of another type are
+/// reported as errors.
 #[inline]
-pub fn get_lit_int<T: FromStr, U: Display>(lit: &Lit, ident: &U) -> Result<T, Error>
+pub fn get_lit_int<T, U: Display>(
+    lit: &Lit,
+    ident: &U,
+    type_name: &str,
+) -> Result<T, AnnotationError>
 where
-    <T as std::str::FromStr>::Err: std::fmt::Display,
+    T: FromStr<Err = ParseIntError>,
 {
     match lit {
-        Lit::Int(lit_int) => Ok(lit_int.base10_parse().unwrap()),
-        _ => Err(Error::new_spanned(
-            lit,
-            format!("expected {} lit to be a integer", ident),
-        )),
+        Lit::Int(lit_int) => {
+            check_suffix(lit, lit_int.suffix(), integer_suffix(type_name), ident)?;
+            let digits = lit_int.base10_digits();
+            digits.parse().map_err(|e: ParseIntError| {
+                let error = match e.kind() {
+                    IntErrorKind::PosOverflow => AnnotationError::from_kind(ErrorKind::Overflow {
+                        value: digits.to_string(),
+                        ty: type_name.to_string(),
+                    }),
+                    IntErrorKind::NegOverflow => AnnotationError::from_kind(ErrorKind::Underflow {
+                        value: digits.to_string(),
+                        ty: type_name.to_string(),
+                    }),
+                    // negative values for unsigned types are reported as invalid digits
+                    IntErrorKind::InvalidDigit if digits.starts_with('-') => {
+                        AnnotationError::from_kind(ErrorKind::Underflow {
+                            value: digits.to_string(),
+                            ty: type_name.to_string(),
+                        })
+                    }
+                    IntErrorKind::Zero => {
+                        AnnotationError::validation(format!("0 is not allowed for {}", type_name))
+                    }
+                    _ => AnnotationError::new(e),
+                };
+                error.with_span(lit.span()).with_path_prefix(ident)
+            })
+        }
+        _ => Err(type_mismatch(lit, ident, "integer")),
     }
 }
 
+/// Parse a float or integer literal into the float type `T` named `type_name`.
 #[inline]
-pub fn get_lit_float<T: FromStr, U: Display>(lit: &Lit, ident: &U) -> Result<T, Error>
+pub fn get_lit_float<T, U: Display>(
+    lit: &Lit,
+    ident: &U,
+    type_name: &str,
+) -> Result<T, AnnotationError>
 where
-    <T as std::str::FromStr>::Err: std::fmt::Display,
+    T: FromStr<Err = ParseFloatError> + Into<f64> + Copy,
 {
+    let digits = match lit {
+        Lit::Float(lit_float) => {
+            check_suffix(lit, lit_float.suffix(), type_name, ident)?;
+            lit_float.base10_digits()
+        }
+        Lit::Int(lit_int) => {
+            check_suffix(lit, lit_int.suffix(), type_name, ident)?;
+            lit_int.base10_digits()
+        }
+        _ => return Err(type_mismatch(lit, ident, "float")),
+    };
+
+    let value: T = digits.parse().map_err(|e: ParseFloatError| {
+        AnnotationError::new(e)
+            .with_span(lit.span())
+            .with_path_prefix(ident)
+    })?;
+    match value.into().is_infinite() {
+        true => Err(AnnotationError::from_kind(match digits.starts_with('-') {
+            true => ErrorKind::Underflow {
+                value: digits.to_string(),
+                ty: type_name.to_string(),
+            },
+            false => ErrorKind::Overflow {
+                value: digits.to_string(),
+                ty: type_name.to_string(),
+            },
+        })
+        .with_span(lit.span())
+        .with_path_prefix(ident)),
+        false => Ok(value),
+    }
+}
+
+#[inline]
+pub fn get_lit_char<U: Display>(lit: &Lit, ident: &U) -> Result<char, AnnotationError> {
     match lit {
-        Lit::Float(lit_float) => Ok(lit_float.base10_parse().unwrap()),
-        _ => Err(Error::new_spanned(
-            lit,
-            format!("expected {} lit to be a float", ident),
-        )),
+        Lit::Char(lit_char) => Ok(lit_char.value()),
+        _ => Err(type_mismatch(lit, ident, "char")),
+    }
+}
+
+// `NonZeroU32` accepts literals suffixed with `u32`
+fn integer_suffix(type_name: &str) -> String {
+    match type_name.strip_prefix("NonZero") {
+        Some(primitive) => primitive.to_lowercase(),
+        None => type_name.to_string(),
+    }
+}
+
+fn check_suffix<S: AsRef<str>, U: Display>(
+    lit: &Lit,
+    suffix: &str,
+    expected: S,
+    ident: &U,
+) -> Result<(), AnnotationError> {
+    match suffix.is_empty() || suffix == expected.as_ref() {
+        true => Ok(()),
+        false => Err(AnnotationError::from_kind(ErrorKind::SuffixMismatch {
+            expected: expected.as_ref().to_string(),
+            found: suffix.to_string(),
+        })
+        .with_span(lit.span())
+        .with_path_prefix(ident)),
     }
 }
 
 #[inline]
-pub fn get_lit_bool<U: Display>(lit: &Lit, ident: &U) -> Result<bool, Error> {
+pub fn get_lit_bool<U: Display>(lit: &Lit, ident: &U) -> Result<bool, AnnotationError> {
     match lit {
         Lit::Bool(lit_bool) => Ok(lit_bool.value),
-        _ => Err(Error::new_spanned(
-            lit,
-            format!("expected {} lit to be a bool", ident),
-        )),
+        _ => Err(type_mismatch(lit, ident, "bool")),
     }
 }
 
+fn type_mismatch<U: Display>(lit: &Lit, ident: &U, expected: &str) -> AnnotationError {
+    AnnotationError::type_mismatch(expected)
+        .with_span(lit.span())
+        .with_path_prefix(ident)
+}
+
+pub fn missing_fields_error(
+    span: Span,
+    annotation: &str,
+    missing_fields: &[&str],
+) -> AnnotationError {
+    AnnotationError::missing_field(annotation, missing_fields).with_span(span)
+}
+
+/// Build the error for an argument no field accepts. `known_keys` are pairs of accepted text and
+/// the key to suggest for it, an unknown key close to one of them gets a suggestion.
+pub fn unexpected_nested_error(
+    nested: &NestedMeta,
+    known_keys: &[(&str, &str)],
+) -> AnnotationError {
+    match nested {
+        NestedMeta::Meta(meta) if !known_keys.is_empty() => {
+            let key = meta.key().to_string();
+            match known_keys
+                .iter()
+                .any(|(_, accepted_key)| *accepted_key == key)
+            {
+                true => {
+                    AnnotationError::new("Unexpected nested value in list").with_span(nested.span())
+                }
+                false => {
+                    AnnotationError::unknown_key(key.as_str(), suggest(key.as_str(), known_keys))
+                        .with_span(meta.key().span())
+                }
+            }
+        }
+        _ => AnnotationError::new("Unexpected nested value in list").with_span(nested.span()),
+    }
+}
+
+/// Build an error for an attribute which looks like a misspelled annotation, attributes not
+/// close to any of `known_annotations` are ignored.
+pub fn unknown_annotation_error(
+    path: &syn::Path,
+    known_annotations: &[(&str, &str)],
+) -> Option<AnnotationError> {
+    let name = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    suggest(name.as_str(), known_annotations)
+        .filter(|suggestion| *suggestion != name)
+        .map(|suggestion| {
+            AnnotationError::from_kind(ErrorKind::UnknownAnnotation {
+                name: name.clone(),
+                suggestion: suggestion.to_string(),
+            })
+            .with_span(path.span())
+        })
+}
+
 pub fn get_mod_path(attrs: &[Attribute]) -> Result<Option<TokenStream>, Error> {
     let mut mod_path = None;
     for attr in attrs.iter() {
@@ -149,3 +302,29 @@ pub fn get_mod_path(attrs: &[Attribute]) -> Result<Option<TokenStream>, Error> {
 
     Ok(mod_path)
 }
+
+/// Convert a syn 2 node into its syn 1 counterpart, like a `syn2::Item` into a `syn::Item`,
+/// to use the syn 1 based parts of the crate from a syn 2 proc macro. Spans are kept.
+#[cfg(feature = "syn2")]
+pub fn from_syn2<T: syn::parse::Parse, N: quote::ToTokens>(node: &N) -> syn2::Result<T> {
+    syn::parse2(node.to_token_stream()).map_err(crate::to_syn2_error)
+}
+
+/// Convert syn 2 attributes into syn 1 attributes, see `from_syn2`.
+#[cfg(feature = "syn2")]
+pub fn attributes_from_syn2(attrs: &[syn2::Attribute]) -> syn2::Result<Vec<Attribute>> {
+    use syn::parse::Parser;
+    attrs
+        .iter()
+        .map(|attr| {
+            let parser = match attr.style {
+                syn2::AttrStyle::Outer => Attribute::parse_outer,
+                syn2::AttrStyle::Inner(_) => Attribute::parse_inner,
+            };
+            parser
+                .parse2(quote::ToTokens::to_token_stream(attr))
+                .map_err(crate::to_syn2_error)
+        })
+        .collect::<syn2::Result<Vec<Vec<Attribute>>>>()
+        .map(|attrs| attrs.into_iter().flatten().collect())
+}
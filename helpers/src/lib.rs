@@ -5,4 +5,35 @@ pub mod symbol;
 pub use crate::symbol::Symbol;
 
 mod error;
-pub use crate::error::Error;
+#[cfg(feature = "syn2")]
+pub use crate::error::to_syn2_error;
+pub use crate::error::{to_syn_error, Error, ErrorKind, IntoErrors};
+
+mod case;
+pub use crate::case::Case;
+
+mod collector;
+pub use crate::collector::ErrorCollector;
+
+mod suggestion;
+pub use crate::suggestion::{suggest, with_suggestion};
+
+mod warning;
+pub use crate::warning::Warning;
+
+pub mod meta;
+
+mod value;
+pub use crate::value::{Value, ValueList, ValueMap, ValueObject};
+
+mod doc;
+pub use crate::doc::DocAnnotation;
+
+mod cfg;
+pub use crate::cfg::{
+    is_cfg_attr, parse_cfg_attr, strip_attribute, unwrap_cfg_attrs, CfgAttrMode, CfgAttribute,
+    CfgSet,
+};
+
+#[cfg(feature = "syn2")]
+pub use syn2;
@@ -0,0 +1,476 @@
+//! A token level representation of annotation arguments.
+//!
+//! `syn::Meta` only accepts literals as values and paths as keys, this module parses the
+//! attribute tokens directly and additionally supports
+//! * negative numbers and constant integer arithmetic: `offset = -1`, `timeout = 60 * 5`
+//! * paths and other expressions as values: `handler = crate::h`
+//! * string literals as keys: `"content-type" = "json"`
+//! * `key => value` as an alternative to `key = value`
+//!
+//! Values which are not literals are handed to the field readers as string literals of their
+//! tokens, so a `String` field accepts `handler = crate::h` as `"crate::h"`.
+
+use crate::Symbol;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use std::fmt::{self, Display};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parenthesized, Attribute, BinOp, Expr, ExprLit, ExprPath, Ident, Lit, LitFloat, LitInt, LitStr,
+    Path, PathSegment, Token, UnOp,
+};
+
+/// Key of an argument, a path or a string literal.
+#[derive(Clone)]
+pub enum Key {
+    Path(Path),
+    Str(LitStr),
+}
+
+impl Key {
+    pub fn span(&self) -> Span {
+        match self {
+            Key::Path(path) => path.span(),
+            Key::Str(lit) => lit.span(),
+        }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Path(path) => write!(f, "{}", path_to_string(path)),
+            Key::Str(lit) => write!(f, "{}", lit.value()),
+        }
+    }
+}
+
+impl PartialEq<Symbol> for Key {
+    fn eq(&self, word: &Symbol) -> bool {
+        match self {
+            Key::Path(path) => path == word,
+            Key::Str(lit) => lit.value() == word.as_str(),
+        }
+    }
+}
+
+impl PartialEq<Symbol> for &Key {
+    fn eq(&self, word: &Symbol) -> bool {
+        (*self).eq(word)
+    }
+}
+
+impl ToTokens for Key {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Key::Path(path) => path.to_tokens(tokens),
+            Key::Str(lit) => lit.to_tokens(tokens),
+        }
+    }
+}
+
+/// Value of an argument. Constant integer expressions and negative numbers are folded into
+/// literals while parsing.
+#[derive(Clone)]
+pub enum Value {
+    Lit(Lit),
+    Path(Path),
+    Expr(Box<Expr>),
+}
+
+impl Value {
+    /// The value as a literal, paths and expressions become string literals of their tokens.
+    pub fn to_lit(&self) -> Lit {
+        match self {
+            Value::Lit(lit) => lit.clone(),
+            Value::Path(path) => Lit::Str(LitStr::new(path_to_string(path).as_str(), path.span())),
+            Value::Expr(expr) => Lit::Str(LitStr::new(
+                expr.to_token_stream().to_string().as_str(),
+                expr.span(),
+            )),
+        }
+    }
+}
+
+impl From<Lit> for Value {
+    fn from(lit: Lit) -> Self {
+        Value::Lit(lit)
+    }
+}
+
+impl From<Expr> for Value {
+    fn from(expr: Expr) -> Self {
+        if let Some(lit) = fold(&expr) {
+            return Value::Lit(lit);
+        }
+
+        match expr {
+            Expr::Lit(ExprLit { lit, .. }) => Value::Lit(lit),
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => Value::Path(path),
+            others => Value::Expr(Box::new(others)),
+        }
+    }
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Value::from(input.parse::<Expr>()?))
+    }
+}
+
+impl ToTokens for Value {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Value::Lit(lit) => lit.to_tokens(tokens),
+            Value::Path(path) => path.to_tokens(tokens),
+            Value::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
+/// `key(nested, ...)`
+#[derive(Clone)]
+pub struct MetaList {
+    pub key: Key,
+    pub nested: Vec<NestedMeta>,
+}
+
+/// `key = value` or `key => value`
+#[derive(Clone)]
+pub struct MetaNameValue {
+    pub key: Key,
+    pub value: Value,
+}
+
+impl ToTokens for MetaList {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.key.to_tokens(tokens);
+        let nested = &self.nested;
+        tokens.extend(quote::quote! { (#(#nested),*) });
+    }
+}
+
+impl ToTokens for MetaNameValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.key.to_tokens(tokens);
+        <Token![=]>::default().to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+#[derive(Clone)]
+pub enum Meta {
+    Path(Key),
+    List(MetaList),
+    NameValue(MetaNameValue),
+}
+
+impl Meta {
+    pub fn key(&self) -> &Key {
+        match self {
+            Meta::Path(key) => key,
+            Meta::List(list) => &list.key,
+            Meta::NameValue(name_value) => &name_value.key,
+        }
+    }
+
+    /// The arguments of an annotation, `Foo` has none and `Foo = 1` is an error.
+    pub fn nested(&self) -> syn::Result<&[NestedMeta]> {
+        match self {
+            Meta::Path(_) => Ok(&[]),
+            Meta::List(list) => Ok(&list.nested),
+            Meta::NameValue(_) => Err(syn::Error::new(
+                self.span(),
+                "Argument of attribute must be a List",
+            )),
+        }
+    }
+
+    /// Parse an attribute like `#[Foo(offset = -1)]`, which `Attribute::parse_meta` rejects.
+    pub fn from_attribute(attribute: &Attribute) -> syn::Result<Self> {
+        let key = Key::Path(attribute.path.clone());
+        (|input: ParseStream| Meta::parse_after_key(key, input)).parse2(attribute.tokens.clone())
+    }
+
+    fn parse_after_key(key: Key, input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Ok(Meta::List(MetaList {
+                key,
+                nested: parse_nested(&content)?,
+            }))
+        } else if input.peek(Token![=>]) || input.peek(Token![=]) {
+            match input.peek(Token![=>]) {
+                true => input.parse::<Token![=>]>().map(|_| ())?,
+                false => input.parse::<Token![=]>().map(|_| ())?,
+            };
+            Ok(Meta::NameValue(MetaNameValue {
+                key,
+                value: input.parse()?,
+            }))
+        } else {
+            match key {
+                Key::Path(_) => Ok(Meta::Path(key)),
+                Key::Str(lit) => Err(syn::Error::new(lit.span(), "expected `=` or `=>`")),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "syn2")]
+impl Meta {
+    /// Convert a syn 2 attribute, the tokens of its arguments are parsed with the grammar of this
+    /// module.
+    pub fn from_syn2_attribute(attribute: &syn2::Attribute) -> syn::Result<Self> {
+        let key = Key::Path(syn::parse2(attribute.path().to_token_stream())?);
+        match &attribute.meta {
+            syn2::Meta::Path(_) => Ok(Meta::Path(key)),
+            syn2::Meta::List(list) => Ok(Meta::List(MetaList {
+                key,
+                nested: parse_args(list.tokens.clone())?,
+            })),
+            syn2::Meta::NameValue(name_value) => Ok(Meta::NameValue(MetaNameValue {
+                key,
+                value: syn::parse2(name_value.value.to_token_stream())?,
+            })),
+        }
+    }
+}
+
+impl Parse for Meta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = match input.peek(LitStr) {
+            true => Key::Str(input.parse()?),
+            false => Key::Path(parse_key_path(input)?),
+        };
+
+        Meta::parse_after_key(key, input)
+    }
+}
+
+impl From<syn::Meta> for Meta {
+    fn from(meta: syn::Meta) -> Self {
+        match meta {
+            syn::Meta::Path(path) => Meta::Path(Key::Path(path)),
+            syn::Meta::List(list) => Meta::List(MetaList {
+                key: Key::Path(list.path),
+                nested: list.nested.into_iter().map(NestedMeta::from).collect(),
+            }),
+            syn::Meta::NameValue(name_value) => Meta::NameValue(MetaNameValue {
+                key: Key::Path(name_value.path),
+                value: Value::Lit(name_value.lit),
+            }),
+        }
+    }
+}
+
+impl ToTokens for Meta {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Meta::Path(key) => key.to_tokens(tokens),
+            Meta::List(list) => list.to_tokens(tokens),
+            Meta::NameValue(name_value) => name_value.to_tokens(tokens),
+        }
+    }
+}
+
+/// An argument in a list, a nested meta or a bare value.
+#[derive(Clone)]
+pub enum NestedMeta {
+    Meta(Meta),
+    Value(Value),
+}
+
+impl NestedMeta {
+    /// The argument as a value. A bare path like `crate::a` is parsed as `Meta::Path`, but is a
+    /// path value where only values are expected.
+    pub fn to_value(&self) -> Option<Value> {
+        match self {
+            NestedMeta::Value(value) => Some(value.clone()),
+            NestedMeta::Meta(Meta::Path(Key::Path(path))) => Some(Value::Path(path.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for NestedMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let is_meta = match input.peek(LitStr) {
+            true => input.peek2(Token![=>]) || input.peek2(Token![=]),
+            false => {
+                // a path followed by anything but `(`, `=`, `=>`, `,` or the end is part of a
+                // value like `a + 1`
+                let fork = input.fork();
+                parse_key_path(&fork).is_ok()
+                    && (fork.is_empty()
+                        || fork.peek(syn::token::Paren)
+                        || fork.peek(Token![=>])
+                        || (fork.peek(Token![=]) && !fork.peek(Token![==]))
+                        || fork.peek(Token![,]))
+            }
+        };
+
+        match is_meta {
+            true => input.parse().map(NestedMeta::Meta),
+            false => input.parse().map(NestedMeta::Value),
+        }
+    }
+}
+
+impl From<syn::NestedMeta> for NestedMeta {
+    fn from(nested: syn::NestedMeta) -> Self {
+        match nested {
+            syn::NestedMeta::Meta(meta) => NestedMeta::Meta(Meta::from(meta)),
+            syn::NestedMeta::Lit(lit) => NestedMeta::Value(Value::Lit(lit)),
+        }
+    }
+}
+
+impl ToTokens for NestedMeta {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            NestedMeta::Meta(meta) => meta.to_tokens(tokens),
+            NestedMeta::Value(value) => value.to_tokens(tokens),
+        }
+    }
+}
+
+/// Parse the arguments of a nested meta inside of `syn2::Attribute::parse_nested_meta`, like
+/// `column(name = "id")` in `#[orm(column(name = "id"))]`. A nested meta without arguments has
+/// no arguments, one with a value is an error.
+#[cfg(feature = "syn2")]
+pub fn parse_syn2_nested_meta(meta: &syn2::meta::ParseNestedMeta) -> syn2::Result<Vec<NestedMeta>> {
+    if meta.input.peek(syn2::token::Paren) {
+        let content;
+        syn2::parenthesized!(content in meta.input);
+        let tokens: TokenStream = content.parse()?;
+        parse_args(tokens).map_err(|e| syn2::Error::new(e.span(), e))
+    } else if meta.input.is_empty() || meta.input.peek(syn2::Token![,]) {
+        Ok(Vec::new())
+    } else {
+        Err(meta.error("Argument of attribute must be a List"))
+    }
+}
+
+/// Parse comma separated arguments, like the tokens of a function-like macro call.
+pub fn parse_args(tokens: TokenStream) -> syn::Result<Vec<NestedMeta>> {
+    parse_nested.parse2(tokens)
+}
+
+fn parse_nested(input: ParseStream) -> syn::Result<Vec<NestedMeta>> {
+    Ok(
+        Punctuated::<NestedMeta, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect(),
+    )
+}
+
+// like `syn::Path::parse_mod_style`, but keywords like `type` are allowed as keys
+fn parse_key_path(input: ParseStream) -> syn::Result<Path> {
+    let leading_colon: Option<Token![::]> = input.parse()?;
+    let mut segments = Punctuated::new();
+    loop {
+        segments.push_value(PathSegment::from(Ident::parse_any(input)?));
+        if !input.peek(Token![::]) {
+            break;
+        }
+        segments.push_punct(input.parse()?);
+    }
+
+    Ok(Path {
+        leading_colon,
+        segments,
+    })
+}
+
+fn path_to_string(path: &Path) -> String {
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    match path.leading_colon {
+        Some(_) => format!("::{}", segments.join("::")),
+        None => segments.join("::"),
+    }
+}
+
+enum Number {
+    Int(i128, String),
+    Float(String, String),
+}
+
+// fold negative numbers and integer arithmetic on literals into a literal
+fn fold(expr: &Expr) -> Option<Lit> {
+    match fold_number(expr)? {
+        Number::Int(value, suffix) => Some(Lit::Int(LitInt::new(
+            format!("{}{}", value, suffix).as_str(),
+            expr.span(),
+        ))),
+        Number::Float(digits, suffix) => Some(Lit::Float(LitFloat::new(
+            format!("{}{}", digits, suffix).as_str(),
+            expr.span(),
+        ))),
+    }
+}
+
+fn fold_number(expr: &Expr) -> Option<Number> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => Some(Number::Int(
+            lit.base10_parse().ok()?,
+            lit.suffix().to_string(),
+        )),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => Some(Number::Float(
+            lit.base10_digits().to_string(),
+            lit.suffix().to_string(),
+        )),
+        Expr::Paren(paren) => fold_number(&paren.expr),
+        #[cfg(feature = "full")]
+        Expr::Group(group) => fold_number(&group.expr),
+        Expr::Unary(unary) => match (&unary.op, fold_number(&unary.expr)?) {
+            (UnOp::Neg(_), Number::Int(value, suffix)) => {
+                Some(Number::Int(value.checked_neg()?, suffix))
+            }
+            (UnOp::Neg(_), Number::Float(digits, suffix)) => Some(Number::Float(
+                match digits.strip_prefix('-') {
+                    Some(positive) => positive.to_string(),
+                    None => format!("-{}", digits),
+                },
+                suffix,
+            )),
+            _ => None,
+        },
+        Expr::Binary(binary) => match (fold_number(&binary.left)?, fold_number(&binary.right)?) {
+            (Number::Int(left, left_suffix), Number::Int(right, right_suffix)) => {
+                let suffix = match (left_suffix.is_empty(), right_suffix.is_empty()) {
+                    (true, _) => right_suffix,
+                    (_, true) => left_suffix,
+                    _ if left_suffix == right_suffix => left_suffix,
+                    _ => return None,
+                };
+                let value = match binary.op {
+                    BinOp::Add(_) => left.checked_add(right),
+                    BinOp::Sub(_) => left.checked_sub(right),
+                    BinOp::Mul(_) => left.checked_mul(right),
+                    BinOp::Div(_) => left.checked_div(right),
+                    BinOp::Rem(_) => left.checked_rem(right),
+                    _ => None,
+                }?;
+                Some(Number::Int(value, suffix))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+// Optimal string alignment distance, so that swapped characters count as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            distances[i][j] = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distances[i][j] = distances[i][j].min(distances[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Find the candidate closest to `value`, if it is close enough to be a likely misspelling.
+/// Candidates are pairs of the text to compare with and the suggestion to return for it.
+pub fn suggest<'a>(value: &str, candidates: &[(&str, &'a str)]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|(candidate, suggestion)| (edit_distance(value, candidate), candidate, *suggestion))
+        .filter(|(distance, candidate, _)| *distance <= (candidate.chars().count() / 3).max(1))
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, _, suggestion)| suggestion)
+}
+
+pub fn with_suggestion(message: String, suggestion: Option<&str>) -> String {
+    match suggestion {
+        Some(suggestion) => format!("{}\n\nhelp: did you mean `{}`?", message, suggestion),
+        None => message,
+    }
+}
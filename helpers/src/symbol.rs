@@ -1,6 +1,11 @@
 use std::fmt::{self, Display};
 use syn::{Ident, Path};
 
+/// The name of an annotation or key, a single identifier like `column` or a path like
+/// `orm::column`.
+///
+/// A path symbol matches the whole path as well as its last segment, so `orm::column` matches
+/// `#[orm::column(...)]` and `#[column(...)]`, for example after a `use orm::column`.
 #[derive(Copy, Clone)]
 pub struct Symbol(&'static str);
 
@@ -8,29 +13,77 @@ impl Symbol {
     pub fn new(path: &'static str) -> Self {
         Symbol(path)
     }
+
+    pub fn segments(&self) -> impl Iterator<Item = &'static str> {
+        self.0.trim_start_matches("::").split("::")
+    }
+
+    /// The last segment, `column` for `orm::column`.
+    pub fn name(&self) -> &'static str {
+        self.segments().last().unwrap_or_default()
+    }
+
+    pub fn is_path(&self) -> bool {
+        self.0.contains("::")
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        let matches_whole = path.segments.len() == self.segments().count()
+            && path
+                .segments
+                .iter()
+                .zip(self.segments())
+                .all(|(segment, name)| segment.ident == name);
+
+        matches_whole || (path.leading_colon.is_none() && path.is_ident(self.name()))
+    }
+}
+
+/// Whether `name` is the last segment of the annotation path `path`, usable in constants. The
+/// readers of `generate_reader!` check their helper attributes with it at compile time.
+pub const fn is_name_of(path: &str, name: &str) -> bool {
+    let path = path.as_bytes();
+    let name = name.as_bytes();
+    if name.len() > path.len() {
+        return false;
+    }
+    let offset = path.len() - name.len();
+    let mut index = 0;
+    while index < name.len() {
+        if path[offset + index] != name[index] {
+            return false;
+        }
+        index += 1;
+    }
+
+    offset == 0 || (offset >= 2 && path[offset - 1] == b':' && path[offset - 2] == b':')
 }
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
-        self == word.0
+        self == word.name()
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Ident {
+impl PartialEq<Symbol> for &Ident {
     fn eq(&self, word: &Symbol) -> bool {
-        *self == word.0
+        *self == word.name()
     }
 }
 
 impl PartialEq<Symbol> for Path {
     fn eq(&self, word: &Symbol) -> bool {
-        self.is_ident(word.0)
+        word.matches_path(self)
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Path {
+impl PartialEq<Symbol> for &Path {
     fn eq(&self, word: &Symbol) -> bool {
-        self.is_ident(word.0)
+        word.matches_path(self)
     }
 }
 
@@ -0,0 +1,279 @@
+use crate::meta::{self, Key, NestedMeta};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Attribute, Lit, LitBool, LitFloat, LitInt};
+
+/// An annotation read without a schema, to inspect annotations which have no Rust type.
+///
+/// The annotation itself is an `Object`. The value of a key `key(...)` is a `List` if it holds
+/// only values, a `Map` if it holds only keys and an `Object` otherwise. Scalars keep their
+/// literal and its span.
+#[derive(Clone)]
+pub enum Value {
+    /// Strings, chars and other literals which are no bool or number. Paths after `=` and
+    /// expressions are read as string literals of their tokens, like annotation fields do.
+    String(Lit),
+    Bool(LitBool),
+    Int(LitInt),
+    Float(LitFloat),
+    /// `key("a", "b")`
+    List(ValueList),
+    /// `key(a = 1, "b" = 2, c(...))`. A bare key like `c` in `Foo(c)` is an empty map, which
+    /// turns back into `c`.
+    Map(ValueMap),
+    /// `Foo("a", b = 1)`
+    Object(ValueObject),
+}
+
+#[derive(Clone)]
+pub struct ValueList {
+    pub items: Vec<Value>,
+    pub span: Span,
+}
+
+#[derive(Clone)]
+pub struct ValueMap {
+    pub entries: Vec<(Key, Value)>,
+    pub span: Span,
+}
+
+/// Positional values come first in the tokens of the object, then the keys.
+#[derive(Clone)]
+pub struct ValueObject {
+    pub path: Key,
+    pub values: Vec<Value>,
+    pub fields: Vec<(Key, Value)>,
+    pub span: Span,
+}
+
+impl ValueMap {
+    /// The first entry of `key`, keys repeat for repeatable nested annotations.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        get_entry(&self.entries, key)
+    }
+}
+
+impl ValueObject {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        get_entry(&self.fields, key)
+    }
+}
+
+impl Value {
+    /// Read an attribute like `#[Foo(offset = -1, bar(1, 2))]`. `#[Foo = "a"]` is read like
+    /// `#[Foo("a")]`.
+    pub fn from_attribute(attribute: &Attribute) -> syn::Result<Self> {
+        meta::Meta::from_attribute(attribute).map(|meta| Value::from(&meta))
+    }
+
+    /// The path of an `Object`, like `Foo` of `Foo(a = 1)`.
+    pub fn path(&self) -> Option<&Key> {
+        match self {
+            Value::Object(object) => Some(&object.path),
+            _ => None,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Value::String(lit) => lit.span(),
+            Value::Bool(lit) => lit.span,
+            Value::Int(lit) => lit.span(),
+            Value::Float(lit) => lit.span(),
+            Value::List(list) => list.span,
+            Value::Map(map) => map.span,
+            Value::Object(object) => object.span,
+        }
+    }
+
+    /// The value of `key` in a `Map` or `Object`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(map) => map.get(key),
+            Value::Object(object) => object.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            Value::String(Lit::Str(lit)) => Some(lit.value()),
+            Value::String(Lit::Char(lit)) => Some(lit.value().to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(lit) => Some(lit.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Value::Int(lit) => lit.base10_parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(lit) => lit.base10_parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(list) => Some(&list.items),
+            _ => None,
+        }
+    }
+
+    /// Turn an `Object` back into the annotation it was read from, to parse it with the schema
+    /// of an annotation type.
+    pub fn to_meta(&self) -> syn::Result<meta::Meta> {
+        match self.path() {
+            Some(_) => syn::parse2(self.to_token_stream()),
+            None => Err(syn::Error::new(
+                self.span(),
+                "Expected an annotation like `Foo(...)`",
+            )),
+        }
+    }
+
+    fn from_value(value: &meta::Value) -> Self {
+        match value.to_lit() {
+            Lit::Bool(lit) => Value::Bool(lit),
+            Lit::Int(lit) => Value::Int(lit),
+            Lit::Float(lit) => Value::Float(lit),
+            lit => Value::String(lit),
+        }
+    }
+
+    fn from_nested(key: &Key, nested: &[NestedMeta], span: Span) -> Self {
+        let (values, fields) = split_nested(nested);
+        match (values.is_empty(), fields.is_empty()) {
+            (_, true) => Value::List(ValueList {
+                items: values,
+                span,
+            }),
+            (true, false) => Value::Map(ValueMap {
+                entries: fields,
+                span,
+            }),
+            (false, false) => Value::Object(ValueObject {
+                path: key.clone(),
+                values,
+                fields,
+                span,
+            }),
+        }
+    }
+
+    // the parenthesized arguments of a list, map or object
+    fn args_tokens(&self) -> Option<TokenStream> {
+        match self {
+            Value::List(list) => {
+                let items = &list.items;
+                Some(quote!((#(#items),*)))
+            }
+            Value::Map(map) => {
+                let entries = map.entries.iter().map(entry_tokens);
+                Some(quote!((#(#entries),*)))
+            }
+            Value::Object(object) => {
+                let values = &object.values;
+                let fields = object.fields.iter().map(entry_tokens);
+                Some(quote!((#(#values,)* #(#fields),*)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<&meta::Meta> for Value {
+    fn from(meta: &meta::Meta) -> Self {
+        let (values, fields) = match meta {
+            meta::Meta::Path(_) => (Vec::new(), Vec::new()),
+            meta::Meta::List(list) => split_nested(&list.nested),
+            meta::Meta::NameValue(name_value) => {
+                (vec![Value::from_value(&name_value.value)], Vec::new())
+            }
+        };
+
+        Value::Object(ValueObject {
+            path: meta.key().clone(),
+            values,
+            fields,
+            span: meta.span(),
+        })
+    }
+}
+
+impl From<&syn::Meta> for Value {
+    fn from(meta: &syn::Meta) -> Self {
+        Value::from(&meta::Meta::from(meta.clone()))
+    }
+}
+
+/// Scalars become literals, an `Object` becomes an annotation like `Foo(a = 1)`.
+impl ToTokens for Value {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Value::String(lit) => lit.to_tokens(tokens),
+            Value::Bool(lit) => lit.to_tokens(tokens),
+            Value::Int(lit) => lit.to_tokens(tokens),
+            Value::Float(lit) => lit.to_tokens(tokens),
+            Value::Object(object) => {
+                object.path.to_tokens(tokens);
+                tokens.extend(self.args_tokens());
+            }
+            Value::List(_) | Value::Map(_) => tokens.extend(self.args_tokens()),
+        }
+    }
+}
+
+fn split_nested(nested: &[NestedMeta]) -> (Vec<Value>, Vec<(Key, Value)>) {
+    let mut values = Vec::new();
+    let mut fields = Vec::new();
+    for nested in nested.iter() {
+        match (nested, nested.to_value()) {
+            (NestedMeta::Meta(meta::Meta::Path(key)), _) => fields.push((
+                key.clone(),
+                Value::Map(ValueMap {
+                    entries: Vec::new(),
+                    span: key.span(),
+                }),
+            )),
+            (_, Some(value)) => values.push(Value::from_value(&value)),
+            (NestedMeta::Meta(meta::Meta::NameValue(name_value)), None) => {
+                fields.push((name_value.key.clone(), Value::from_value(&name_value.value)))
+            }
+            (NestedMeta::Meta(meta @ meta::Meta::List(list)), None) => fields.push((
+                list.key.clone(),
+                Value::from_nested(&list.key, &list.nested, meta.span()),
+            )),
+            (NestedMeta::Value(_), None) => unreachable!(),
+        }
+    }
+
+    (values, fields)
+}
+
+fn entry_tokens((key, value): &(Key, Value)) -> TokenStream {
+    match (value, value.args_tokens()) {
+        (Value::Map(map), _) if map.entries.is_empty() => quote!(#key),
+        (_, Some(args)) => quote!(#key #args),
+        (_, None) => quote!(#key = #value),
+    }
+}
+
+fn get_entry<'a>(entries: &'a [(Key, Value)], key: &str) -> Option<&'a Value> {
+    entries
+        .iter()
+        .find(|(entry_key, _)| entry_key.to_string() == key)
+        .map(|(_, value)| value)
+}
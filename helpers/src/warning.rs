@@ -0,0 +1,59 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use std::fmt::{Display, Formatter, Result};
+
+/// A non-fatal diagnostic, like a deprecated key or a redundant value.
+///
+/// Rust has no stable API to emit warnings from a proc macro, so `to_tokens` expands to a
+/// reference to a `#[deprecated]` constant at `span`, which makes the compiler report `message`
+/// as a deprecation warning at the right place.
+#[derive(Debug, Clone)]
+pub struct Warning {
+    message: String,
+    span: Option<Span>,
+}
+
+impl Warning {
+    pub fn new<T: Display>(message: T) -> Self {
+        Warning {
+            message: message.to_string(),
+            span: None,
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl ToTokens for Warning {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let message = self.message.as_str();
+        let span = self.span.unwrap_or_else(Span::call_site);
+        let usage = quote_spanned! { span => annotation_warning };
+        (quote! {
+            const _: () = {
+                #[deprecated(note = #message)]
+                #[allow(non_upper_case_globals)]
+                const annotation_warning: () = ();
+                #usage
+            };
+        })
+        .to_tokens(tokens);
+    }
+}
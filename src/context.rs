@@ -0,0 +1,154 @@
+use crate::{CfgAttrMode, Warning};
+use std::cell::RefCell;
+use std::rc::Rc;
+use syn::{DeriveInput, Field, GenericParam, Ident, Type, Variant};
+#[cfg(feature = "items")]
+use syn::{FnArg, Pat};
+
+/// Describes the item an annotation is attached to, so that annotation structures can derive
+/// default values from it.
+///
+/// `item_ident` is the annotated item itself (the struct, enum or union of a derive input),
+/// `field_ident` and `field_type` are set when the annotation sits on a field or a variant,
+/// and `enclosing_type` is the type the item is declared in, if any. For a derive input, its
+/// fields and variants are declared in the derived type, so that type is the enclosing one.
+///
+/// `cfg_attr` is set with `with_cfg_attr` to read annotations wrapped in `#[cfg_attr(...)]`,
+/// otherwise they are skipped like any other attribute.
+///
+/// Warnings reported while parsing are collected in the context. Contexts derived with
+/// `with_field` and friends share the warning list of the context they are derived from.
+#[derive(Clone, Default)]
+pub struct AnnotationContext {
+    pub item_ident: Option<Ident>,
+    pub field_ident: Option<Ident>,
+    pub field_type: Option<Type>,
+    pub enclosing_type: Option<Type>,
+    pub cfg_attr: Option<CfgAttrMode>,
+    warnings: Rc<RefCell<Vec<Warning>>>,
+}
+
+impl AnnotationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_derive_input(input: &DeriveInput) -> Self {
+        let ident = &input.ident;
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        AnnotationContext {
+            item_ident: Some(ident.clone()),
+            enclosing_type: Some(syn::parse_quote!(#ident #ty_generics)),
+            ..Self::default()
+        }
+    }
+
+    /// Like `from_derive_input` for the derive input of a syn 2 proc macro, which is converted
+    /// into a syn 1 `DeriveInput` first.
+    #[cfg(feature = "syn2")]
+    pub fn from_syn2_derive_input(input: &crate::syn2::DeriveInput) -> crate::syn2::Result<Self> {
+        crate::from_syn2(input).map(|input| Self::from_derive_input(&input))
+    }
+
+    /// Context of an item nested in the current one, like a method of an impl block.
+    pub fn with_item(&self, ident: Option<&Ident>) -> Self {
+        AnnotationContext {
+            item_ident: ident.cloned(),
+            field_ident: None,
+            field_type: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_field(&self, field: &Field) -> Self {
+        AnnotationContext {
+            field_ident: field.ident.clone(),
+            field_type: Some(field.ty.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Context of a function parameter, the parameter is the field. `field_ident` is only set
+    /// for parameters bound to a plain ident like `id: i32`, and is `self` for receivers.
+    #[cfg(feature = "items")]
+    pub fn with_fn_arg(&self, arg: &FnArg) -> Self {
+        let (field_ident, field_type) = match arg {
+            FnArg::Receiver(receiver) => (
+                Some(Ident::new("self", receiver.self_token.span)),
+                self.enclosing_type.clone(),
+            ),
+            FnArg::Typed(pat_type) => (
+                match pat_type.pat.as_ref() {
+                    Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                    _ => None,
+                },
+                Some(pat_type.ty.as_ref().clone()),
+            ),
+        };
+        AnnotationContext {
+            field_ident,
+            field_type,
+            ..self.clone()
+        }
+    }
+
+    /// Context of a generic parameter, its ident is the field, and const parameters have their
+    /// type as `field_type`.
+    pub fn with_generic_param(&self, param: &GenericParam) -> Self {
+        let (field_ident, field_type) = match param {
+            GenericParam::Type(type_param) => (type_param.ident.clone(), None),
+            GenericParam::Lifetime(lifetime_def) => (lifetime_def.lifetime.ident.clone(), None),
+            GenericParam::Const(const_param) => {
+                (const_param.ident.clone(), Some(const_param.ty.clone()))
+            }
+        };
+        AnnotationContext {
+            field_ident: Some(field_ident),
+            field_type,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_variant(&self, variant: &Variant) -> Self {
+        AnnotationContext {
+            field_ident: Some(variant.ident.clone()),
+            field_type: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_enclosing_type(&self, enclosing_type: Type) -> Self {
+        AnnotationContext {
+            enclosing_type: Some(enclosing_type),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_cfg_attr(&self, mode: CfgAttrMode) -> Self {
+        AnnotationContext {
+            cfg_attr: Some(mode),
+            ..self.clone()
+        }
+    }
+
+    pub fn item_name(&self) -> Option<String> {
+        self.item_ident.as_ref().map(|ident| ident.to_string())
+    }
+
+    pub fn field_name(&self) -> Option<String> {
+        self.field_ident.as_ref().map(|ident| ident.to_string())
+    }
+
+    pub fn warn(&self, warning: Warning) {
+        self.warnings.borrow_mut().push(warning)
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow().clone()
+    }
+
+    /// Remove and return all warnings collected so far.
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow_mut().drain(..).collect()
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    AnnotatedGenericParam, AnnotatedParam, AnnotationContext, AnnotationSet, ErrorCollector,
+};
+use syn::{Attribute, Error, Generics, Ident, ImplItem, Item, Signature, TraitItem};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Const,
+    Enum,
+    ExternCrate,
+    Fn,
+    ForeignMod,
+    Impl,
+    Macro,
+    Method,
+    Mod,
+    Static,
+    Struct,
+    Trait,
+    TraitAlias,
+    Type,
+    Union,
+    Use,
+    /// A field of a struct or union, only used by `Scanner`.
+    Field,
+    /// A variant of an enum, only used by `Scanner`.
+    Variant,
+    Other,
+}
+
+/// An item of a module, an impl block or a trait.
+#[derive(Clone, Copy)]
+pub enum ItemRef<'a> {
+    Item(&'a Item),
+    ImplItem(&'a ImplItem),
+    TraitItem(&'a TraitItem),
+}
+
+impl<'a> ItemRef<'a> {
+    pub fn kind(&self) -> ItemKind {
+        match self {
+            ItemRef::Item(item) => match item {
+                Item::Const(_) => ItemKind::Const,
+                Item::Enum(_) => ItemKind::Enum,
+                Item::ExternCrate(_) => ItemKind::ExternCrate,
+                Item::Fn(_) => ItemKind::Fn,
+                Item::ForeignMod(_) => ItemKind::ForeignMod,
+                Item::Impl(_) => ItemKind::Impl,
+                Item::Macro(_) | Item::Macro2(_) => ItemKind::Macro,
+                Item::Mod(_) => ItemKind::Mod,
+                Item::Static(_) => ItemKind::Static,
+                Item::Struct(_) => ItemKind::Struct,
+                Item::Trait(_) => ItemKind::Trait,
+                Item::TraitAlias(_) => ItemKind::TraitAlias,
+                Item::Type(_) => ItemKind::Type,
+                Item::Union(_) => ItemKind::Union,
+                Item::Use(_) => ItemKind::Use,
+                _ => ItemKind::Other,
+            },
+            ItemRef::ImplItem(item) => match item {
+                ImplItem::Const(_) => ItemKind::Const,
+                ImplItem::Method(_) => ItemKind::Method,
+                ImplItem::Type(_) => ItemKind::Type,
+                ImplItem::Macro(_) => ItemKind::Macro,
+                _ => ItemKind::Other,
+            },
+            ItemRef::TraitItem(item) => match item {
+                TraitItem::Const(_) => ItemKind::Const,
+                TraitItem::Method(_) => ItemKind::Method,
+                TraitItem::Type(_) => ItemKind::Type,
+                TraitItem::Macro(_) => ItemKind::Macro,
+                _ => ItemKind::Other,
+            },
+        }
+    }
+
+    /// The name of the item, `None` for impl blocks, uses, foreign mods and unnamed macros.
+    pub fn ident(&self) -> Option<&'a Ident> {
+        match *self {
+            ItemRef::Item(item) => match item {
+                Item::Const(item) => Some(&item.ident),
+                Item::Enum(item) => Some(&item.ident),
+                Item::ExternCrate(item) => Some(&item.ident),
+                Item::Fn(item) => Some(&item.sig.ident),
+                Item::Macro(item) => item.ident.as_ref(),
+                Item::Macro2(item) => Some(&item.ident),
+                Item::Mod(item) => Some(&item.ident),
+                Item::Static(item) => Some(&item.ident),
+                Item::Struct(item) => Some(&item.ident),
+                Item::Trait(item) => Some(&item.ident),
+                Item::TraitAlias(item) => Some(&item.ident),
+                Item::Type(item) => Some(&item.ident),
+                Item::Union(item) => Some(&item.ident),
+                _ => None,
+            },
+            ItemRef::ImplItem(item) => match item {
+                ImplItem::Const(item) => Some(&item.ident),
+                ImplItem::Method(item) => Some(&item.sig.ident),
+                ImplItem::Type(item) => Some(&item.ident),
+                _ => None,
+            },
+            ItemRef::TraitItem(item) => match item {
+                TraitItem::Const(item) => Some(&item.ident),
+                TraitItem::Method(item) => Some(&item.sig.ident),
+                TraitItem::Type(item) => Some(&item.ident),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn attrs(&self) -> &'a [Attribute] {
+        match *self {
+            ItemRef::Item(item) => match item {
+                Item::Const(item) => &item.attrs,
+                Item::Enum(item) => &item.attrs,
+                Item::ExternCrate(item) => &item.attrs,
+                Item::Fn(item) => &item.attrs,
+                Item::ForeignMod(item) => &item.attrs,
+                Item::Impl(item) => &item.attrs,
+                Item::Macro(item) => &item.attrs,
+                Item::Macro2(item) => &item.attrs,
+                Item::Mod(item) => &item.attrs,
+                Item::Static(item) => &item.attrs,
+                Item::Struct(item) => &item.attrs,
+                Item::Trait(item) => &item.attrs,
+                Item::TraitAlias(item) => &item.attrs,
+                Item::Type(item) => &item.attrs,
+                Item::Union(item) => &item.attrs,
+                Item::Use(item) => &item.attrs,
+                _ => &[],
+            },
+            ItemRef::ImplItem(item) => match item {
+                ImplItem::Const(item) => &item.attrs,
+                ImplItem::Method(item) => &item.attrs,
+                ImplItem::Type(item) => &item.attrs,
+                ImplItem::Macro(item) => &item.attrs,
+                _ => &[],
+            },
+            ItemRef::TraitItem(item) => match item {
+                TraitItem::Const(item) => &item.attrs,
+                TraitItem::Method(item) => &item.attrs,
+                TraitItem::Type(item) => &item.attrs,
+                TraitItem::Macro(item) => &item.attrs,
+                _ => &[],
+            },
+        }
+    }
+
+    /// The signature of functions and methods.
+    pub fn sig(&self) -> Option<&'a Signature> {
+        match *self {
+            ItemRef::Item(Item::Fn(item)) => Some(&item.sig),
+            ItemRef::ImplItem(ImplItem::Method(item)) => Some(&item.sig),
+            ItemRef::TraitItem(TraitItem::Method(item)) => Some(&item.sig),
+            _ => None,
+        }
+    }
+
+    pub fn generics(&self) -> Option<&'a Generics> {
+        match *self {
+            ItemRef::Item(item) => match item {
+                Item::Enum(item) => Some(&item.generics),
+                Item::Fn(item) => Some(&item.sig.generics),
+                Item::Impl(item) => Some(&item.generics),
+                Item::Struct(item) => Some(&item.generics),
+                Item::Trait(item) => Some(&item.generics),
+                Item::TraitAlias(item) => Some(&item.generics),
+                Item::Type(item) => Some(&item.generics),
+                Item::Union(item) => Some(&item.generics),
+                _ => None,
+            },
+            ItemRef::ImplItem(item) => match item {
+                ImplItem::Method(item) => Some(&item.sig.generics),
+                ImplItem::Type(item) => Some(&item.generics),
+                _ => None,
+            },
+            ItemRef::TraitItem(item) => match item {
+                TraitItem::Method(item) => Some(&item.sig.generics),
+                TraitItem::Type(item) => Some(&item.generics),
+                _ => None,
+            },
+        }
+    }
+
+    /// Items of inline modules, impl blocks and traits.
+    pub fn children(&self) -> Vec<ItemRef<'a>> {
+        match *self {
+            ItemRef::Item(Item::Mod(item)) => match &item.content {
+                Some((_, items)) => items.iter().map(ItemRef::Item).collect(),
+                None => Vec::new(),
+            },
+            ItemRef::Item(Item::Impl(item)) => item.items.iter().map(ItemRef::ImplItem).collect(),
+            ItemRef::Item(Item::Trait(item)) => item.items.iter().map(ItemRef::TraitItem).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// An item with its parsed annotations, and the annotations of its parameters, generic
+/// parameters and children.
+pub struct AnnotatedItem<'a, S: AnnotationSet> {
+    pub item: ItemRef<'a>,
+    pub kind: ItemKind,
+    pub ident: Option<&'a Ident>,
+    pub annotations: S,
+    pub params: Vec<AnnotatedParam<'a, S>>,
+    pub generics: Vec<AnnotatedGenericParam<'a, S>>,
+    pub children: Vec<AnnotatedItem<'a, S>>,
+}
+
+impl<'a, S: AnnotationSet> AnnotatedItem<'a, S> {
+    pub fn from_item(item: &'a Item) -> Result<Self, Error> {
+        Self::walk(ItemRef::Item(item), &AnnotationContext::default())
+    }
+
+    pub fn from_impl_item(item: &'a ImplItem) -> Result<Self, Error> {
+        Self::walk(ItemRef::ImplItem(item), &AnnotationContext::default())
+    }
+
+    pub fn from_trait_item(item: &'a TraitItem) -> Result<Self, Error> {
+        Self::walk(ItemRef::TraitItem(item), &AnnotationContext::default())
+    }
+
+    pub fn from_items(items: &'a [Item]) -> Result<Vec<Self>, Error> {
+        let mut errors = ErrorCollector::new();
+        let items = items
+            .iter()
+            .filter_map(|item| errors.handle(Self::from_item(item)))
+            .collect();
+
+        errors.finish_with(items)
+    }
+
+    /// Parse the annotations of `item`, its parameters and children. The context of an item
+    /// in an impl block has the implemented type as `enclosing_type`.
+    pub fn walk(item: ItemRef<'a>, context: &AnnotationContext) -> Result<Self, Error> {
+        let context = context.with_item(item.ident());
+        let mut errors = ErrorCollector::new();
+        let annotations = errors.handle(S::from_attributes_with_context(item.attrs(), &context));
+        let params = match item.sig() {
+            Some(sig) => errors
+                .handle(AnnotatedParam::from_signature_with_context(sig, &context))
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let generics = match item.generics() {
+            Some(generics) => errors
+                .handle(AnnotatedGenericParam::from_generics_with_context(
+                    generics, &context,
+                ))
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let children_context = match item {
+            ItemRef::Item(Item::Impl(item_impl)) => {
+                context.with_enclosing_type(item_impl.self_ty.as_ref().clone())
+            }
+            _ => context.clone(),
+        };
+        let children: Vec<AnnotatedItem<'a, S>> = item
+            .children()
+            .into_iter()
+            .filter_map(|child| errors.handle(Self::walk(child, &children_context)))
+            .collect();
+
+        errors.finish()?;
+        Ok(AnnotatedItem {
+            item,
+            kind: item.kind(),
+            ident: item.ident(),
+            annotations: annotations.unwrap(),
+            params,
+            generics,
+            children,
+        })
+    }
+
+    /// This item and all its descendants, depth first.
+    pub fn descendants(&self) -> Vec<&Self> {
+        let mut items = vec![self];
+        for child in self.children.iter() {
+            items.extend(child.descendants());
+        }
+
+        items
+    }
+}
@@ -1,15 +1,46 @@
 mod traits;
 pub use traits::*;
 
+mod context;
+pub use context::AnnotationContext;
+
+mod member;
+pub use member::{AnnotatedMember, AnnotationSet, Member};
+
+mod target;
+pub use target::AnnotationTarget;
+
+#[cfg(feature = "items")]
+mod item;
+#[cfg(feature = "items")]
+pub use item::{AnnotatedItem, ItemKind, ItemRef};
+
+mod param;
+pub use param::AnnotatedGenericParam;
+#[cfg(feature = "items")]
+pub use param::AnnotatedParam;
+
+#[cfg(feature = "annotation_reader")]
+mod reader;
+#[cfg(feature = "annotation_reader")]
+pub use reader::{check_unread_annotations, expand_with_item, remove_annotations};
+
+#[cfg(feature = "scanner")]
+mod scanner;
+#[cfg(feature = "scanner")]
+pub use scanner::{AnnotationIndex, IndexEntry, IndexedAnnotations, Scanner};
+
 pub use helpers::*;
 
 #[doc(hidden)]
-pub use derive::{Annotation, AnnotationEnumValue};
+pub use derive::{Annotation, AnnotationEnumValue, AnnotationGroup, AnnotationTarget};
 
-#[cfg(any(feature = "annotation_reader"))]
-pub use derive::{__get_annotation, __has_annotation, generate_reader};
+#[cfg(feature = "annotation_reader")]
+pub use derive::{
+    __get_annotation, __get_annotations, __has_annotation, generate_attribute, generate_reader,
+};
 
-#[cfg(any(feature = "annotation_reader"))]
+#[cfg(feature = "annotation_reader")]
 #[macro_export]
 macro_rules! get_annotation {
     ($class: ident, $annotation: ident) => {
@@ -20,7 +51,20 @@ macro_rules! get_annotation {
     };
 }
 
-#[cfg(any(feature = "annotation_reader"))]
+/// Get all occurrences of a repeatable annotation as `Vec`. Other annotations are merged, so
+/// the `Vec` holds at most one.
+#[cfg(feature = "annotation_reader")]
+#[macro_export]
+macro_rules! get_annotations {
+    ($class: ident, $annotation: ident) => {
+        $crate::__get_annotations!($class, $annotation)
+    };
+    ($class: ident :: $prop: ident, $annotation: ident) => {
+        $crate::__get_annotations!($class, $annotation, $prop)
+    };
+}
+
+#[cfg(feature = "annotation_reader")]
 #[macro_export]
 macro_rules! has_annotation {
     ($class: ident :: $prop: ident, $annotation: ident) => {
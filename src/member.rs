@@ -0,0 +1,228 @@
+#[cfg(feature = "items")]
+use crate::ItemRef;
+use crate::{AnnotationContext, AnnotationStructure, AnnotationStructures, ErrorCollector};
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Ident, Type};
+#[cfg(feature = "items")]
+use syn::{FnArg, Item, Pat};
+
+/// A field of a struct or union, a variant of an enum, or a member of an item, see
+/// `from_item_with_context`.
+///
+/// `ident` is `None` for the fields of a tuple struct and parameters bound to a pattern, and
+/// `self` for receivers like `AnnotatedParam::ident`. `ty` is `None` for variants and items. `context` describes the member, so that annotations can take
+/// their defaults from it.
+pub struct Member<'a> {
+    pub ident: Option<Ident>,
+    pub index: usize,
+    pub ty: Option<&'a Type>,
+    pub attrs: &'a [Attribute],
+    pub context: AnnotationContext,
+}
+
+impl<'a> Member<'a> {
+    pub fn from_derive_input(input: &'a DeriveInput) -> Vec<Self> {
+        Self::from_derive_input_with_context(input, &AnnotationContext::from_derive_input(input))
+    }
+
+    /// Members of `input`, their contexts are derived from `context` and share its warnings.
+    pub fn from_derive_input_with_context(
+        input: &'a DeriveInput,
+        context: &AnnotationContext,
+    ) -> Vec<Self> {
+        match &input.data {
+            Data::Struct(data_struct) => data_struct
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| Member {
+                    ident: field.ident.clone(),
+                    index,
+                    ty: Some(&field.ty),
+                    attrs: &field.attrs,
+                    context: context.with_field(field),
+                })
+                .collect(),
+            Data::Enum(data_enum) => data_enum
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| Member {
+                    ident: Some(variant.ident.clone()),
+                    index,
+                    ty: None,
+                    attrs: &variant.attrs,
+                    context: context.with_variant(variant),
+                })
+                .collect(),
+            Data::Union(data_union) => data_union
+                .fields
+                .named
+                .iter()
+                .enumerate()
+                .map(|(index, field)| Member {
+                    ident: field.ident.clone(),
+                    index,
+                    ty: Some(&field.ty),
+                    attrs: &field.attrs,
+                    context: context.with_field(field),
+                })
+                .collect(),
+        }
+    }
+
+    /// Members of an item: the parameters of a function, or the named items of an impl block, trait
+    /// or inline module. Items of an impl block have the implemented type as `enclosing_type`.
+    #[cfg(feature = "items")]
+    pub fn from_item_with_context(item: &'a Item, context: &AnnotationContext) -> Vec<Self> {
+        match item {
+            Item::Fn(item_fn) => item_fn
+                .sig
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(index, arg)| {
+                    let (ident, ty, attrs) = match arg {
+                        FnArg::Receiver(receiver) => (
+                            Some(Ident::new("self", receiver.self_token.span)),
+                            None,
+                            &receiver.attrs,
+                        ),
+                        FnArg::Typed(pat_type) => (
+                            match pat_type.pat.as_ref() {
+                                Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                                _ => None,
+                            },
+                            Some(pat_type.ty.as_ref()),
+                            &pat_type.attrs,
+                        ),
+                    };
+                    Member {
+                        ident,
+                        index,
+                        ty,
+                        attrs,
+                        context: context.with_fn_arg(arg),
+                    }
+                })
+                .collect(),
+            Item::Impl(_) | Item::Trait(_) | Item::Mod(_) => {
+                let context = match item {
+                    Item::Impl(item_impl) => {
+                        context.with_enclosing_type(item_impl.self_ty.as_ref().clone())
+                    }
+                    _ => context.clone(),
+                };
+                ItemRef::Item(item)
+                    .children()
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, child)| {
+                        child.ident().map(|ident| Member {
+                            ident: Some(ident.clone()),
+                            index,
+                            ty: None,
+                            attrs: child.attrs(),
+                            context: context.with_item(Some(ident)),
+                        })
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The ident of the member, or its index for the fields of a tuple struct.
+    pub fn name(&self) -> String {
+        match &self.ident {
+            Some(ident) => ident.to_string(),
+            None => self.index.to_string(),
+        }
+    }
+
+    /// The span of the ident, or of the type for the fields of a tuple struct.
+    pub fn span(&self) -> Span {
+        match (&self.ident, self.ty) {
+            (Some(ident), _) => ident.span(),
+            (None, Some(ty)) => ty.span(),
+            (None, None) => Span::call_site(),
+        }
+    }
+
+    pub fn annotations<S: AnnotationSet>(&self) -> Result<S, Error> {
+        S::from_attributes_with_context(self.attrs, &self.context)
+    }
+}
+
+/// A member together with its parsed annotations.
+pub struct AnnotatedMember<'a, S: AnnotationSet> {
+    pub member: Member<'a>,
+    pub annotations: S,
+}
+
+impl<'a, S: AnnotationSet> AnnotatedMember<'a, S> {
+    /// Parse the annotations of every member of `input`, errors of all members are reported
+    /// together.
+    pub fn from_derive_input(input: &'a DeriveInput) -> Result<Vec<Self>, Error> {
+        let mut errors = ErrorCollector::new();
+        let members: Vec<Self> = Member::from_derive_input(input)
+            .into_iter()
+            .filter_map(|member| {
+                errors
+                    .handle(member.annotations())
+                    .map(|annotations| AnnotatedMember {
+                        member,
+                        annotations,
+                    })
+            })
+            .collect();
+
+        errors.finish_with(members)
+    }
+}
+
+/// Annotations parsed from the same attributes: `AnnotationStructures<T>` for one annotation
+/// type, or a tuple of them for several.
+pub trait AnnotationSet: Sized {
+    fn from_attributes_with_context(
+        attrs: &[Attribute],
+        context: &AnnotationContext,
+    ) -> Result<Self, Error>;
+}
+
+impl<T: AnnotationStructure> AnnotationSet for AnnotationStructures<T> {
+    fn from_attributes_with_context(
+        attrs: &[Attribute],
+        context: &AnnotationContext,
+    ) -> Result<Self, Error> {
+        AnnotationStructures::from_attributes_with_context(attrs, context)
+    }
+}
+
+macro_rules! impl_annotation_set {
+    ($($set: ident),+) => {
+        impl<$($set: AnnotationSet),+> AnnotationSet for ($($set,)+) {
+            #[allow(non_snake_case)]
+            fn from_attributes_with_context(
+                attrs: &[Attribute],
+                context: &AnnotationContext,
+            ) -> Result<Self, Error> {
+                let mut errors = ErrorCollector::new();
+                $(
+                    let $set = errors.handle($set::from_attributes_with_context(attrs, context));
+                )+
+
+                errors.finish()?;
+                Ok(($($set.unwrap(),)+))
+            }
+        }
+    };
+}
+
+impl_annotation_set!(A);
+impl_annotation_set!(A, B);
+impl_annotation_set!(A, B, C);
+impl_annotation_set!(A, B, C, D);
+impl_annotation_set!(A, B, C, D, E);
+impl_annotation_set!(A, B, C, D, E, F);
@@ -0,0 +1,128 @@
+use crate::{AnnotationContext, AnnotationSet, ErrorCollector};
+use syn::{Attribute, Error, GenericParam, Generics, Ident};
+#[cfg(feature = "items")]
+use syn::{FnArg, Pat, Signature, Type};
+
+/// A parameter of a function or method, `index` counts the `self` receiver.
+///
+/// `pat` and `ty` are `None` for a `self` receiver without a type.
+#[cfg(feature = "items")]
+pub struct AnnotatedParam<'a, S: AnnotationSet> {
+    pub index: usize,
+    pub arg: &'a FnArg,
+    pub pat: Option<&'a Pat>,
+    pub ty: Option<&'a Type>,
+    pub annotations: S,
+}
+
+#[cfg(feature = "items")]
+impl<'a, S: AnnotationSet> AnnotatedParam<'a, S> {
+    pub fn from_signature(sig: &'a Signature) -> Result<Vec<Self>, Error> {
+        Self::from_signature_with_context(sig, &AnnotationContext::default())
+    }
+
+    /// Parse the annotations of all parameters of `sig`, like `#[Path("id")]` in
+    /// `fn handler(#[Path("id")] id: u64)`.
+    pub fn from_signature_with_context(
+        sig: &'a Signature,
+        context: &AnnotationContext,
+    ) -> Result<Vec<Self>, Error> {
+        let context = context.with_item(Some(&sig.ident));
+        let mut errors = ErrorCollector::new();
+        let params = sig
+            .inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, arg)| {
+                let (attrs, pat, ty) = match arg {
+                    FnArg::Receiver(receiver) => (&receiver.attrs, None, None),
+                    FnArg::Typed(pat_type) => (
+                        &pat_type.attrs,
+                        Some(pat_type.pat.as_ref()),
+                        Some(pat_type.ty.as_ref()),
+                    ),
+                };
+                errors
+                    .handle(S::from_attributes_with_context(
+                        attrs,
+                        &context.with_fn_arg(arg),
+                    ))
+                    .map(|annotations| AnnotatedParam {
+                        index,
+                        arg,
+                        pat,
+                        ty,
+                        annotations,
+                    })
+            })
+            .collect();
+
+        errors.finish_with(params)
+    }
+
+    /// The ident of a parameter bound to a plain ident like `id: u64`, `self` for receivers,
+    /// like the `field_ident` of `AnnotationContext::with_fn_arg`.
+    pub fn ident(&self) -> Option<Ident> {
+        match self.arg {
+            FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                _ => None,
+            },
+            FnArg::Receiver(receiver) => Some(Ident::new("self", receiver.self_token.span)),
+        }
+    }
+}
+
+/// A type, lifetime or const parameter with its parsed annotations, like `#[Bound("Send")]` in
+/// `struct Foo<#[Bound("Send")] T>`.
+pub struct AnnotatedGenericParam<'a, S: AnnotationSet> {
+    pub index: usize,
+    pub param: &'a GenericParam,
+    /// The name of the parameter, `T`, `N` or `a` for `'a`.
+    pub ident: &'a Ident,
+    pub annotations: S,
+}
+
+impl<'a, S: AnnotationSet> AnnotatedGenericParam<'a, S> {
+    pub fn from_generics(generics: &'a Generics) -> Result<Vec<Self>, Error> {
+        Self::from_generics_with_context(generics, &AnnotationContext::default())
+    }
+
+    pub fn from_generics_with_context(
+        generics: &'a Generics,
+        context: &AnnotationContext,
+    ) -> Result<Vec<Self>, Error> {
+        let mut errors = ErrorCollector::new();
+        let params = generics
+            .params
+            .iter()
+            .enumerate()
+            .filter_map(|(index, param)| {
+                let (attrs, ident): (&[Attribute], _) = match param {
+                    GenericParam::Type(type_param) => (&type_param.attrs, &type_param.ident),
+                    GenericParam::Lifetime(lifetime_def) => {
+                        (&lifetime_def.attrs, &lifetime_def.lifetime.ident)
+                    }
+                    GenericParam::Const(const_param) => (&const_param.attrs, &const_param.ident),
+                };
+                errors
+                    .handle(S::from_attributes_with_context(
+                        attrs,
+                        &context.with_generic_param(param),
+                    ))
+                    .map(|annotations| AnnotatedGenericParam {
+                        index,
+                        param,
+                        ident,
+                        annotations,
+                    })
+            })
+            .collect();
+
+        errors.finish_with(params)
+    }
+
+    pub fn is_lifetime(&self) -> bool {
+        matches!(self.param, GenericParam::Lifetime(_))
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    is_cfg_attr, parse_cfg_attr, strip_attribute, Case, ErrorCollector, ItemKind, ItemRef, Symbol,
+};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Error, FnArg, GenericParam, Generics, ImplItem, Item, Signature, TraitItem};
+
+fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    Some(match item {
+        Item::Const(item) => &mut item.attrs,
+        Item::Enum(item) => &mut item.attrs,
+        Item::ExternCrate(item) => &mut item.attrs,
+        Item::Fn(item) => &mut item.attrs,
+        Item::ForeignMod(item) => &mut item.attrs,
+        Item::Impl(item) => &mut item.attrs,
+        Item::Macro(item) => &mut item.attrs,
+        Item::Macro2(item) => &mut item.attrs,
+        Item::Mod(item) => &mut item.attrs,
+        Item::Static(item) => &mut item.attrs,
+        Item::Struct(item) => &mut item.attrs,
+        Item::Trait(item) => &mut item.attrs,
+        Item::TraitAlias(item) => &mut item.attrs,
+        Item::Type(item) => &mut item.attrs,
+        Item::Union(item) => &mut item.attrs,
+        Item::Use(item) => &mut item.attrs,
+        _ => return None,
+    })
+}
+
+fn impl_item_attrs_mut(item: &mut ImplItem) -> Option<&mut Vec<Attribute>> {
+    Some(match item {
+        ImplItem::Const(item) => &mut item.attrs,
+        ImplItem::Method(item) => &mut item.attrs,
+        ImplItem::Type(item) => &mut item.attrs,
+        ImplItem::Macro(item) => &mut item.attrs,
+        _ => return None,
+    })
+}
+
+fn trait_item_attrs_mut(item: &mut TraitItem) -> Option<&mut Vec<Attribute>> {
+    Some(match item {
+        TraitItem::Const(item) => &mut item.attrs,
+        TraitItem::Method(item) => &mut item.attrs,
+        TraitItem::Type(item) => &mut item.attrs,
+        TraitItem::Macro(item) => &mut item.attrs,
+        _ => return None,
+    })
+}
+
+fn fn_arg_attrs(arg: &FnArg) -> &[Attribute] {
+    match arg {
+        FnArg::Receiver(receiver) => &receiver.attrs,
+        FnArg::Typed(pat_type) => &pat_type.attrs,
+    }
+}
+
+fn fn_arg_attrs_mut(arg: &mut FnArg) -> &mut Vec<Attribute> {
+    match arg {
+        FnArg::Receiver(receiver) => &mut receiver.attrs,
+        FnArg::Typed(pat_type) => &mut pat_type.attrs,
+    }
+}
+
+fn generic_param_attrs(param: &GenericParam) -> &[Attribute] {
+    match param {
+        GenericParam::Type(param) => &param.attrs,
+        GenericParam::Lifetime(param) => &param.attrs,
+        GenericParam::Const(param) => &param.attrs,
+    }
+}
+
+fn generic_param_attrs_mut(param: &mut GenericParam) -> &mut Vec<Attribute> {
+    match param {
+        GenericParam::Type(param) => &mut param.attrs,
+        GenericParam::Lifetime(param) => &mut param.attrs,
+        GenericParam::Const(param) => &mut param.attrs,
+    }
+}
+
+type Retain<'a> = dyn FnMut(&mut Vec<Attribute>) + 'a;
+
+fn retain_generics(generics: &mut Generics, retain: &mut Retain) {
+    for param in generics.params.iter_mut() {
+        retain(generic_param_attrs_mut(param));
+    }
+}
+
+fn retain_signature(sig: &mut Signature, retain: &mut Retain) {
+    retain_generics(&mut sig.generics, retain);
+    for arg in sig.inputs.iter_mut() {
+        retain(fn_arg_attrs_mut(arg));
+    }
+}
+
+fn retain_item(item: &mut Item, retain: &mut Retain) {
+    if let Some(attrs) = item_attrs_mut(item) {
+        retain(attrs);
+    }
+    match item {
+        Item::Enum(item) => retain_generics(&mut item.generics, retain),
+        Item::Fn(item) => retain_signature(&mut item.sig, retain),
+        Item::Impl(item) => {
+            retain_generics(&mut item.generics, retain);
+            for impl_item in item.items.iter_mut() {
+                retain_impl_item(impl_item, retain);
+            }
+        }
+        Item::Mod(item) => {
+            if let Some((_, items)) = &mut item.content {
+                for item in items.iter_mut() {
+                    retain_item(item, retain);
+                }
+            }
+        }
+        Item::Struct(item) => retain_generics(&mut item.generics, retain),
+        Item::Trait(item) => {
+            retain_generics(&mut item.generics, retain);
+            for trait_item in item.items.iter_mut() {
+                retain_trait_item(trait_item, retain);
+            }
+        }
+        Item::TraitAlias(item) => retain_generics(&mut item.generics, retain),
+        Item::Type(item) => retain_generics(&mut item.generics, retain),
+        Item::Union(item) => retain_generics(&mut item.generics, retain),
+        _ => {}
+    }
+}
+
+fn retain_impl_item(item: &mut ImplItem, retain: &mut Retain) {
+    if let Some(attrs) = impl_item_attrs_mut(item) {
+        retain(attrs);
+    }
+    match item {
+        ImplItem::Method(item) => retain_signature(&mut item.sig, retain),
+        ImplItem::Type(item) => retain_generics(&mut item.generics, retain),
+        _ => {}
+    }
+}
+
+fn retain_trait_item(item: &mut TraitItem, retain: &mut Retain) {
+    if let Some(attrs) = trait_item_attrs_mut(item) {
+        retain(attrs);
+    }
+    match item {
+        TraitItem::Method(item) => retain_signature(&mut item.sig, retain),
+        TraitItem::Type(item) => retain_generics(&mut item.generics, retain),
+        _ => {}
+    }
+}
+
+/// Remove the annotations of `paths` from `item` and everything in it, so that the compiler does
+/// not see them as unknown attributes, also inside of `cfg_attr`. The whole item is visited like
+/// `AnnotatedItem::walk` does: items, their parameters and generic parameters, and the items of
+/// inline modules, impl blocks and traits.
+pub fn remove_annotations(item: &mut Item, paths: &[Symbol]) {
+    let remove = |attr: &Attribute| paths.iter().any(|path| attr.path == *path);
+    retain_item(item, &mut |attrs: &mut Vec<Attribute>| {
+        *attrs = attrs
+            .drain(..)
+            .filter_map(|attr| strip_attribute(attr, &remove))
+            .collect()
+    });
+}
+
+/// Report the annotations of `paths` which readers do not read, but `remove_annotations`
+/// removes. Readers read the annotations of the item and of its members, see
+/// `Member::from_item_with_context`, use `AnnotatedItem` to read the others.
+pub fn check_unread_annotations(item: &Item, paths: &[Symbol]) -> Result<(), Error> {
+    let mut errors = ErrorCollector::new();
+    check_unread_item(ItemRef::Item(item), 0, paths, &mut errors);
+
+    errors.finish()
+}
+
+// `depth` is 0 for the item of the reader and 1 for its members
+fn check_unread_item(item: ItemRef, depth: usize, paths: &[Symbol], errors: &mut ErrorCollector) {
+    let mut report = |attrs: &[Attribute], message: &str| {
+        for attr in attrs.iter() {
+            let unwrapped = match is_cfg_attr(attr) {
+                true => parse_cfg_attr(attr)
+                    .map(|(_, attrs)| attrs)
+                    .unwrap_or_default(),
+                false => vec![attr.clone()],
+            };
+            for attr in unwrapped
+                .iter()
+                .filter(|attr| paths.iter().any(|path| attr.path == *path))
+            {
+                errors.push(Error::new_spanned(attr, message));
+            }
+        }
+    };
+
+    // members without a name, like impl blocks in a module, have no accessors to read them from
+    if depth > 1 || (depth == 1 && item.ident().is_none()) {
+        report(
+            item.attrs(),
+            "Annotations of nested items are not read by attribute readers, only those of the \
+             item and of its named members",
+        );
+    }
+    if let Some(generics) = item.generics() {
+        for param in generics.params.iter() {
+            report(
+                generic_param_attrs(param),
+                "Annotations of generic parameters are not read by attribute readers",
+            );
+        }
+    }
+    // the parameters of a function are its members
+    if let (Some(sig), false) = (item.sig(), depth == 0) {
+        let message = match item.kind() {
+            ItemKind::Method => {
+                "Annotations of method parameters are not read by attribute readers"
+            }
+            _ => "Annotations of function parameters are not read by attribute readers",
+        };
+        for arg in sig.inputs.iter() {
+            report(fn_arg_attrs(arg), message);
+        }
+    }
+    for child in item.children() {
+        check_unread_item(child, depth + 1, paths, errors);
+    }
+}
+
+/// Expand `item` together with the generated `tokens`: in an inherent impl of the implemented
+/// type, at the end of an inline module, or in a module named after a function or, in snake case,
+/// after a trait.
+pub fn expand_with_item(item: &Item, tokens: TokenStream) -> Result<TokenStream, Error> {
+    match item {
+        Item::Fn(item_fn) => {
+            let vis = &item_fn.vis;
+            let ident = &item_fn.sig.ident;
+            Ok(quote! {
+                #item_fn
+
+                #vis mod #ident {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #tokens
+                }
+            })
+        }
+        Item::Impl(item_impl) => {
+            let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+            let self_ty = &item_impl.self_ty;
+            Ok(quote! {
+                #item_impl
+
+                impl #impl_generics #self_ty #where_clause {
+                    #tokens
+                }
+            })
+        }
+        Item::Trait(item_trait) => {
+            let vis = &item_trait.vis;
+            let ident = format_ident!(
+                "{}",
+                Case::Snake.convert(&item_trait.ident.to_string()),
+                span = item_trait.ident.span()
+            );
+            Ok(quote! {
+                #item_trait
+
+                #vis mod #ident {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #tokens
+                }
+            })
+        }
+        Item::Mod(item_mod) if item_mod.content.is_some() => {
+            let mut item_mod = item_mod.clone();
+            if let Some((_, items)) = &mut item_mod.content {
+                items.push(Item::Verbatim(tokens));
+            }
+            Ok(quote!(#item_mod))
+        }
+        _ => Err(Error::new_spanned(
+            item,
+            "Attribute reader must be placed on a function, an impl block, a trait or an inline module",
+        )),
+    }
+}
@@ -0,0 +1,541 @@
+use crate::meta::{self, Key, NestedMeta};
+use crate::{
+    unwrap_cfg_attrs, AnnotationContext, AnnotationSet, AnnotationStructure, AnnotationStructures,
+    CfgAttrMode, CfgAttribute, ErrorCollector, ItemKind, ItemRef, Member, Symbol,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{Attribute, DeriveInput, Error, Item, Lit, Type};
+
+/// Annotation sets an `AnnotationIndex` can be built from: `AnnotationStructures<T>` of
+/// annotations which implement `ToTokens`, or tuples of them.
+pub trait IndexedAnnotations: AnnotationSet {
+    fn paths() -> Vec<Symbol>;
+
+    /// The type of `to_value_tokens()` in the generated Rust code, an error if the path of an
+    /// annotation type is unknown.
+    fn type_tokens() -> Result<TokenStream, Error>;
+
+    fn to_value_tokens(&self) -> TokenStream;
+
+    fn is_empty(&self) -> bool;
+}
+
+impl<T: AnnotationStructure + ToTokens> IndexedAnnotations for AnnotationStructures<T> {
+    fn paths() -> Vec<Symbol> {
+        vec![T::get_path()]
+    }
+
+    fn type_tokens() -> Result<TokenStream, Error> {
+        let type_path = T::TYPE_PATH.ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                format!(
+                    "The type path of annotation `{}` is unknown, set `TYPE_PATH`",
+                    T::get_path()
+                ),
+            )
+        })?;
+        let ty: Type = syn::parse_str(type_path)?;
+        Ok(quote!(Vec<#ty>))
+    }
+
+    fn to_value_tokens(&self) -> TokenStream {
+        let attrs = self.attrs();
+        quote!(vec![#(#attrs),*])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.attrs().is_empty()
+    }
+}
+
+macro_rules! impl_indexed_annotations {
+    ($($set: ident: $index: tt),+) => {
+        impl<$($set: IndexedAnnotations),+> IndexedAnnotations for ($($set,)+) {
+            fn paths() -> Vec<Symbol> {
+                [$($set::paths()),+].concat()
+            }
+
+            #[allow(non_snake_case)]
+            fn type_tokens() -> Result<TokenStream, Error> {
+                $(let $set = $set::type_tokens()?;)+
+                Ok(quote!(($(#$set,)+)))
+            }
+
+            #[allow(non_snake_case)]
+            fn to_value_tokens(&self) -> TokenStream {
+                $(let $set = self.$index.to_value_tokens();)+
+                quote!(($(#$set,)+))
+            }
+
+            fn is_empty(&self) -> bool {
+                $(self.$index.is_empty())&&+
+            }
+        }
+    };
+}
+
+impl_indexed_annotations!(A: 0);
+impl_indexed_annotations!(A: 0, B: 1);
+impl_indexed_annotations!(A: 0, B: 1, C: 2);
+impl_indexed_annotations!(A: 0, B: 1, C: 2, D: 3);
+impl_indexed_annotations!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_indexed_annotations!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+/// An annotated item, field, variant or member of an impl block or trait.
+pub struct IndexEntry<S: IndexedAnnotations> {
+    /// Like `crate::api::users::get`, or `crate::api::User::find` for members.
+    pub path: String,
+    pub kind: ItemKind,
+    pub file: PathBuf,
+    pub annotations: S,
+    /// The annotations as written, for `AnnotationIndex::to_json()`.
+    pub metas: Vec<meta::Meta>,
+}
+
+pub struct AnnotationIndex<S: IndexedAnnotations> {
+    pub entries: Vec<IndexEntry<S>>,
+}
+
+impl<S: IndexedAnnotations> AnnotationIndex<S> {
+    pub fn get(&self, path: &str) -> Option<&IndexEntry<S>> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    /// A function `fn #fn_name() -> Vec<(&'static str, S)>` which returns the parsed annotations
+    /// with the path of their items, to `include!` it from `OUT_DIR`. The annotation types are
+    /// named by their `TYPE_PATH`.
+    pub fn to_rust(&self, fn_name: &str) -> Result<TokenStream, Error> {
+        let fn_name = syn::Ident::new(fn_name, Span::call_site());
+        let ty = S::type_tokens()?;
+        let paths = self.entries.iter().map(|entry| entry.path.as_str());
+        let values = self
+            .entries
+            .iter()
+            .map(|entry| entry.annotations.to_value_tokens());
+
+        Ok(quote! {
+            #[allow(clippy::type_complexity)]
+            pub fn #fn_name() -> Vec<(&'static str, #ty)> {
+                vec![#((#paths, #values)),*]
+            }
+        })
+    }
+
+    /// The annotations as written, without defaults: keys become object members, lists of
+    /// values arrays and keys without value `true`.
+    pub fn to_json(&self) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let annotations: Vec<String> = entry
+                    .metas
+                    .iter()
+                    .map(|meta| {
+                        format!(
+                            "{{\"name\":{},\"args\":{}}}",
+                            json_string(&meta.key().to_string()),
+                            meta_to_json(meta)
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"path\":{},\"kind\":{},\"file\":{},\"annotations\":[{}]}}",
+                    json_string(&entry.path),
+                    json_string(&format!("{:?}", entry.kind)),
+                    json_string(&entry.file.to_string_lossy()),
+                    annotations.join(",")
+                )
+            })
+            .collect();
+
+        format!("[{}]", entries.join(","))
+    }
+
+    pub fn write_rust<P: AsRef<Path>>(&self, path: P, fn_name: &str) -> std::io::Result<()> {
+        let rust = self
+            .to_rust(fn_name)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        fs::write(path, rust.to_string())
+    }
+
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+/// Scans the sources of a crate for annotations, to generate code from them in `build.rs`.
+///
+/// Scanning starts at `lib.rs` or `main.rs` of the source directory and follows `mod`
+/// declarations, like the compiler does.
+pub struct Scanner {
+    dir: PathBuf,
+    cfg_attr: Option<CfgAttrMode>,
+}
+
+impl Scanner {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Scanner {
+            dir: dir.as_ref().to_path_buf(),
+            cfg_attr: None,
+        }
+    }
+
+    /// Read annotations wrapped in `cfg_attr`, which are not expanded in the sources.
+    pub fn with_cfg_attr(mut self, mode: CfgAttrMode) -> Self {
+        self.cfg_attr = Some(mode);
+        self
+    }
+
+    /// Errors of all files are reported together, their messages start with the file.
+    pub fn scan<S: IndexedAnnotations>(&self) -> Result<AnnotationIndex<S>, Error> {
+        let root = ["lib.rs", "main.rs"]
+            .iter()
+            .map(|name| self.dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                Error::new(
+                    Span::call_site(),
+                    format!("No lib.rs or main.rs in {}", self.dir.display()),
+                )
+            })?;
+        let mut errors = ErrorCollector::new();
+        let mut entries = Vec::new();
+        self.scan_file(
+            &root,
+            &ModuleDirs::new(&self.dir),
+            &["crate".to_string()],
+            &mut entries,
+            &mut errors,
+        );
+
+        errors.finish_with(AnnotationIndex { entries })
+    }
+
+    fn scan_file<S: IndexedAnnotations>(
+        &self,
+        file: &Path,
+        dirs: &ModuleDirs,
+        module: &[String],
+        entries: &mut Vec<IndexEntry<S>>,
+        errors: &mut ErrorCollector,
+    ) {
+        let with_file = |e: Error| file_error(file, e);
+        let source = match fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(e) => {
+                errors.push(with_file(Error::new(Span::call_site(), e)));
+                return;
+            }
+        };
+        match syn::parse_file(&source) {
+            Ok(parsed) => self.scan_items(&parsed.items, file, dirs, module, entries, errors),
+            Err(e) => errors.push(with_file(e)),
+        }
+    }
+
+    fn scan_items<S: IndexedAnnotations>(
+        &self,
+        items: &[Item],
+        file: &Path,
+        dirs: &ModuleDirs,
+        module: &[String],
+        entries: &mut Vec<IndexEntry<S>>,
+        errors: &mut ErrorCollector,
+    ) {
+        let context = match &self.cfg_attr {
+            Some(mode) => AnnotationContext::new().with_cfg_attr(mode.clone()),
+            None => AnnotationContext::new(),
+        };
+        for item in items.iter() {
+            let item_ref = ItemRef::Item(item);
+            let context = context.with_item(item_ref.ident());
+            let path = |name: &str| [module, &[name.to_string()]].concat();
+            if let Some(ident) = item_ref.ident() {
+                self.add_entry(
+                    &path(&ident.to_string()),
+                    item_ref.kind(),
+                    item_ref.attrs(),
+                    &context,
+                    file,
+                    entries,
+                    errors,
+                );
+            }
+
+            match item {
+                Item::Struct(_) | Item::Enum(_) | Item::Union(_) => {
+                    let input = item_to_derive_input(item);
+                    let parent = path(&input.ident.to_string());
+                    let kind = match item {
+                        Item::Enum(_) => ItemKind::Variant,
+                        _ => ItemKind::Field,
+                    };
+                    for member in Member::from_derive_input_with_context(&input, &context) {
+                        self.add_entry(
+                            &[parent.as_slice(), &[member.name()]].concat(),
+                            kind,
+                            member.attrs,
+                            &member.context,
+                            file,
+                            entries,
+                            errors,
+                        );
+                    }
+                }
+                Item::Impl(_) | Item::Trait(_) => {
+                    let parent = match item {
+                        Item::Impl(item_impl) => match item_impl.self_ty.as_ref() {
+                            Type::Path(type_path) => type_path
+                                .path
+                                .segments
+                                .last()
+                                .map(|segment| segment.ident.to_string()),
+                            _ => None,
+                        },
+                        _ => item_ref.ident().map(|ident| ident.to_string()),
+                    };
+                    let context = match item {
+                        Item::Impl(item_impl) => {
+                            context.with_enclosing_type(item_impl.self_ty.as_ref().clone())
+                        }
+                        _ => context.clone(),
+                    };
+                    if let Some(parent) = parent {
+                        for child in item_ref.children() {
+                            if let Some(ident) = child.ident() {
+                                self.add_entry(
+                                    &[module, &[parent.clone(), ident.to_string()]].concat(),
+                                    child.kind(),
+                                    child.attrs(),
+                                    &context.with_item(Some(ident)),
+                                    file,
+                                    entries,
+                                    errors,
+                                );
+                            }
+                        }
+                    }
+                }
+                Item::Mod(item_mod) => {
+                    let name = item_mod.ident.to_string();
+                    let module = path(&name);
+                    match &item_mod.content {
+                        Some((_, items)) => {
+                            let inline_dirs = ModuleDirs::new(&dirs.dir.join(&name));
+                            self.scan_items(items, file, &inline_dirs, &module, entries, errors)
+                        }
+                        None => match module_file(&item_mod.attrs, dirs, &name) {
+                            Some((mod_file, mod_dirs)) => {
+                                self.scan_file(&mod_file, &mod_dirs, &module, entries, errors)
+                            }
+                            None => errors.push(file_error(
+                                file,
+                                Error::new_spanned(
+                                    &item_mod.ident,
+                                    format!("File of module `{}` not found", name),
+                                ),
+                            )),
+                        },
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_entry<S: IndexedAnnotations>(
+        &self,
+        path: &[String],
+        kind: ItemKind,
+        attrs: &[Attribute],
+        context: &AnnotationContext,
+        file: &Path,
+        entries: &mut Vec<IndexEntry<S>>,
+        errors: &mut ErrorCollector,
+    ) {
+        let annotations = match S::from_attributes_with_context(attrs, context) {
+            Ok(annotations) => annotations,
+            Err(e) => return errors.push(file_error(file, e)),
+        };
+        if annotations.is_empty() {
+            return;
+        }
+        let attrs: Vec<CfgAttribute> = match &context.cfg_attr {
+            Some(mode) => unwrap_cfg_attrs(attrs, mode).unwrap_or_default(),
+            None => attrs.iter().map(CfgAttribute::from).collect(),
+        };
+        let paths = S::paths();
+
+        entries.push(IndexEntry {
+            path: path.join("::"),
+            kind,
+            file: file.to_path_buf(),
+            annotations,
+            metas: attrs
+                .iter()
+                .filter(|attr| paths.iter().any(|path| attr.attr.path == *path))
+                .filter_map(|attr| meta::Meta::from_attribute(&attr.attr).ok())
+                .collect(),
+        });
+    }
+}
+
+fn item_to_derive_input(item: &Item) -> DeriveInput {
+    match item.clone() {
+        Item::Struct(item) => item.into(),
+        Item::Enum(item) => item.into(),
+        Item::Union(item) => item.into(),
+        _ => unreachable!(),
+    }
+}
+
+/// Where the `mod` declarations of a file or inline module are looked up: `name.rs` and
+/// `name/mod.rs` in `dir`, `#[path = "..."]` relative to `path_dir`. Like rustc does, they differ
+/// at the top of a file which is no `mod.rs`, where `#[path]` is relative to the directory of the
+/// file.
+struct ModuleDirs {
+    dir: PathBuf,
+    path_dir: PathBuf,
+}
+
+impl ModuleDirs {
+    fn new(dir: &Path) -> Self {
+        ModuleDirs {
+            dir: dir.to_path_buf(),
+            path_dir: dir.to_path_buf(),
+        }
+    }
+}
+
+/// The file of `mod name;` and the directories of its child modules. Files of `#[path]` and
+/// `mod.rs` files have their child modules next to them.
+fn module_file(
+    attrs: &[Attribute],
+    dirs: &ModuleDirs,
+    name: &str,
+) -> Option<(PathBuf, ModuleDirs)> {
+    let path_attr = attrs.iter().find_map(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::NameValue(name_value)) if name_value.path.is_ident("path") => {
+            match name_value.lit {
+                Lit::Str(path) => Some(path.value()),
+                _ => None,
+            }
+        }
+        _ => None,
+    });
+    if let Some(path) = path_attr {
+        let file = dirs.path_dir.join(path);
+        let mod_dirs = ModuleDirs::new(file.parent().unwrap_or(&dirs.path_dir));
+        return Some((file, mod_dirs));
+    }
+
+    let file = dirs.dir.join(format!("{}.rs", name));
+    if file.is_file() {
+        let mod_dirs = ModuleDirs {
+            dir: dirs.dir.join(name),
+            path_dir: dirs.dir.clone(),
+        };
+        return Some((file, mod_dirs));
+    }
+    let file = dirs.dir.join(name).join("mod.rs");
+    match file.is_file() {
+        true => Some((file, ModuleDirs::new(&dirs.dir.join(name)))),
+        false => None,
+    }
+}
+
+fn file_error(file: &Path, error: Error) -> Error {
+    let mut errors = error.into_iter().map(|e| {
+        let start = e.span().start();
+        Error::new(
+            e.span(),
+            format!(
+                "{}:{}:{}: {}",
+                file.display(),
+                start.line,
+                start.column + 1,
+                e
+            ),
+        )
+    });
+    let mut combined = errors.next().unwrap();
+    for e in errors {
+        combined.combine(e);
+    }
+
+    combined
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+fn value_to_json(value: &meta::Value) -> String {
+    match value.to_lit() {
+        Lit::Str(lit) => json_string(&lit.value()),
+        Lit::Int(lit) => lit.base10_digits().to_string(),
+        Lit::Float(lit) => lit.base10_digits().to_string(),
+        Lit::Bool(lit) => lit.value.to_string(),
+        Lit::Char(lit) => json_string(&lit.value().to_string()),
+        lit => json_string(&lit.to_token_stream().to_string()),
+    }
+}
+
+fn nested_to_json(nested: &[NestedMeta]) -> String {
+    let is_object = nested
+        .iter()
+        .all(|nested| matches!(nested, NestedMeta::Meta(_)));
+    let members: Vec<String> = nested
+        .iter()
+        .map(|nested| match nested {
+            NestedMeta::Meta(meta) => {
+                let member = format!("{}:{}", key_to_json(meta.key()), meta_to_json(meta));
+                match is_object {
+                    true => member,
+                    false => format!("{{{}}}", member),
+                }
+            }
+            NestedMeta::Value(value) => value_to_json(value),
+        })
+        .collect();
+
+    match is_object {
+        true => format!("{{{}}}", members.join(",")),
+        false => format!("[{}]", members.join(",")),
+    }
+}
+
+fn key_to_json(key: &Key) -> String {
+    match key {
+        Key::Str(lit) => json_string(&lit.value()),
+        key => json_string(&key.to_string()),
+    }
+}
+
+fn meta_to_json(meta: &meta::Meta) -> String {
+    match meta {
+        meta::Meta::Path(_) => "true".to_string(),
+        meta::Meta::List(list) => nested_to_json(&list.nested),
+        meta::Meta::NameValue(name_value) => value_to_json(&name_value.value),
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Member;
+use syn::{DeriveInput, Error};
+
+/// A receiver of everything a derive consumes, generated by `#[derive(AnnotationTarget)]`.
+///
+/// A receiver is read from a derive input, or from a member (a field or variant) of it when it
+/// is used in the `fields` of another receiver. The derive implements the methods its fields
+/// are available for, the others report an error.
+pub trait AnnotationTarget: Sized {
+    fn from_derive_input(input: &DeriveInput) -> Result<Self, Error> {
+        Err(Error::new_spanned(
+            &input.ident,
+            "This annotation target can't be read from a derive input",
+        ))
+    }
+
+    fn from_member(member: &Member) -> Result<Self, Error> {
+        Err(Error::new(
+            member.span(),
+            "This annotation target can't be read from a field or variant",
+        ))
+    }
+}
@@ -1,43 +1,316 @@
-use crate::Symbol;
-use syn::parse::{Parse, ParseBuffer};
-use syn::{AttributeArgs, DeriveInput, Error, Meta};
+use crate::{
+    meta, to_syn_error, unwrap_cfg_attrs, AnnotationContext, CfgAttribute, DocAnnotation,
+    Error as AnnotationError, ErrorCollector, Symbol, Warning,
+};
+#[cfg(feature = "syn2")]
+use crate::{syn2, to_syn2_error};
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseBuffer, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, AttributeArgs, DeriveInput, Error, Meta, Token};
 
+/// An annotation parsed from attributes.
+///
+/// Implement either `from_meta()` and `from_attribute_args()`, or `from_nested_meta_lenient()`,
+/// which the derive does. The defaults of each bridge to the other, so any input can be parsed
+/// through `from_nested_meta_lenient()`: turn it into arguments with `annotation_rs::meta`, like
+/// `meta::parse_args()` for tokens or `meta::Meta::nested()` for an attribute.
 pub trait AnnotationStructure {
     fn get_path() -> Symbol
     where
         Self: Sized;
 
+    /// The path `get_path()` returns, known at compile time. The derive sets it, so that
+    /// `generate_reader!` can check the helper attribute of the annotation.
+    const PATH: Option<&'static str> = None;
+
+    /// The path of the type in generated code, the one its `ToTokens` implementation constructs
+    /// it with. The derive sets it from `#[mod_path]`, the `Scanner` names the type with it.
+    const TYPE_PATH: Option<&'static str> = None;
+
     fn from_meta(input: &Meta) -> Result<Self, syn::Error>
     where
-        Self: std::marker::Sized;
+        Self: std::marker::Sized,
+    {
+        let meta = meta::Meta::from(input.clone());
+        Self::parse_nested_meta(meta.nested()?, &AnnotationContext::default(), input.span())
+            .map_err(to_syn_error)
+    }
 
     fn from_attribute_args(input: AttributeArgs) -> Result<Self, syn::Error>
     where
-        Self: std::marker::Sized;
+        Self: std::marker::Sized,
+    {
+        let nested: Vec<meta::NestedMeta> = input.into_iter().map(meta::NestedMeta::from).collect();
+        Self::parse_nested_meta(&nested, &AnnotationContext::default(), Span::call_site())
+            .map_err(to_syn_error)
+    }
+
+    /// Parse the arguments of an annotation as far as possible and return the errors alongside
+    /// the structure, errors which do not belong to any argument are reported at `span`.
+    ///
+    /// Invalid or missing values fall back to the default of the field, or `Default::default()`
+    /// of its type. The structure is `None` only if a required `Object` or `Enum` field has no
+    /// valid value, because such types have no fallback. The default parses the arguments with
+    /// `from_attribute_args()`, arguments which `syn::AttributeArgs` does not accept are errors.
+    fn from_nested_meta_lenient(
+        input: &[meta::NestedMeta],
+        _context: &AnnotationContext,
+        _span: Span,
+    ) -> (Option<Self>, Vec<AnnotationError>)
+    where
+        Self: std::marker::Sized,
+    {
+        let args = Punctuated::<syn::NestedMeta, Token![,]>::parse_terminated
+            .parse2(quote::quote!(#(#input),*))
+            .and_then(|args| Self::from_attribute_args(args.into_iter().collect()));
+        match args {
+            Ok(value) => (Some(value), Vec::new()),
+            Err(e) => (None, AnnotationError::from_syn(e)),
+        }
+    }
+
+    /// Strict version of `from_nested_meta_lenient`, any error fails the parsing.
+    fn parse_nested_meta(
+        input: &[meta::NestedMeta],
+        context: &AnnotationContext,
+        span: Span,
+    ) -> Result<Self, Vec<AnnotationError>>
+    where
+        Self: std::marker::Sized,
+    {
+        match Self::from_nested_meta_lenient(input, context, span) {
+            (Some(value), errors) if errors.is_empty() => Ok(value),
+            (_, errors) => Err(errors),
+        }
+    }
+
+    /// Declared by `#[annotation(repeatable)]`, all occurrences of the annotation are kept.
+    fn is_repeatable() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Declared by `#[annotation(unique)]`, the annotation may appear only once, even in
+    /// `AnnotationStructures`.
+    fn is_unique() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Merge a repeated occurrence of the annotation into this one. Annotation groups merge
+    /// their members, other annotations may appear only once.
+    fn merge(&mut self, _other: Self) -> Result<(), AnnotationError>
+    where
+        Self: std::marker::Sized,
+    {
+        Err(AnnotationError::new(format!(
+            "Annotation `{}` may appear only once",
+            Self::get_path()
+        )))
+    }
+}
+
+/// Parse an annotation read with the grammar of `annotation_rs::meta`.
+fn parse_annotation<T: AnnotationStructure>(
+    input: syn::Result<meta::Meta>,
+    context: &AnnotationContext,
+) -> Result<T, Vec<AnnotationError>> {
+    let meta = input.map_err(AnnotationError::from_syn)?;
+    let nested = meta.nested().map_err(AnnotationError::from_syn)?;
+    T::parse_nested_meta(nested, context, meta.span())
 }
 
+/// The annotations of type `T` on an item, each kept with the span and `cfg_attr` predicate of
+/// its attribute. Annotations pushed to `attrs` afterwards have neither, errors about them are
+/// reported at the call site.
 pub struct AnnotationStructures<T: AnnotationStructure> {
     pub attrs: Vec<T>,
+    /// Non-fatal issues found while parsing, emit them with `quote!` to show them to the user.
+    pub warnings: Vec<Warning>,
+    spans: Vec<Span>,
+    predicates: Vec<Option<syn::Meta>>,
 }
 
 impl<T: AnnotationStructure> AnnotationStructures<T> {
     pub fn from_derive_input(derive_input: &DeriveInput) -> Result<Self, Error> {
-        let attributes: Vec<T> = derive_input
-            .attrs
+        let context = AnnotationContext::from_derive_input(derive_input);
+        Self::from_attributes_with_context(&derive_input.attrs, &context)
+    }
+
+    /// Like `from_derive_input` for the derive input of a syn 2 proc macro. The input is converted
+    /// into a syn 1 `DeriveInput` first, errors are converted into a `syn2::Error`.
+    #[cfg(feature = "syn2")]
+    pub fn from_syn2_derive_input(derive_input: &syn2::DeriveInput) -> syn2::Result<Self> {
+        let derive_input: DeriveInput = crate::from_syn2(derive_input)?;
+        Self::from_derive_input(&derive_input).map_err(to_syn2_error)
+    }
+
+    /// Like `from_attributes_with_context` for the attributes of a syn 2 item.
+    #[cfg(feature = "syn2")]
+    pub fn from_syn2_attributes_with_context(
+        attrs: &[syn2::Attribute],
+        context: &AnnotationContext,
+    ) -> syn2::Result<Self> {
+        let attrs = crate::attributes_from_syn2(attrs)?;
+        Self::from_attributes_with_context(&attrs, context).map_err(to_syn2_error)
+    }
+
+    /// Parse the attributes of a field, variant or any other item, attributes whose path does
+    /// not match `T::get_path()` are skipped.
+    pub fn from_attributes(attrs: &[Attribute]) -> Result<Self, Error> {
+        Self::from_attributes_with_context(attrs, &AnnotationContext::default())
+    }
+
+    pub fn from_attributes_with_context(
+        attrs: &[Attribute],
+        context: &AnnotationContext,
+    ) -> Result<Self, Error> {
+        let attrs: Vec<CfgAttribute> = match &context.cfg_attr {
+            Some(mode) => unwrap_cfg_attrs(attrs, mode)?,
+            None => attrs.iter().map(CfgAttribute::from).collect(),
+        };
+        let matched: Vec<&CfgAttribute> = attrs
             .iter()
-            .map(|attr| match attr.parse_meta() {
-                Ok(meta) => T::from_meta(&meta),
-                Err(e) => Err(e),
-            })
-            .collect::<Result<Vec<T>, Error>>()?;
+            .filter(|attr| attr.attr.path == T::get_path())
+            .collect();
+        let attributes: Vec<T> = ErrorCollector::collect(
+            matched
+                .iter()
+                .map(|attr| parse_annotation(meta::Meta::from_attribute(&attr.attr), context)),
+        )
+        .map_err(to_syn_error)?;
+
+        Self::from_parsed(
+            attributes,
+            matched.iter().map(|attr| attr.attr.span()).collect(),
+            matched.iter().map(|attr| attr.predicate.clone()).collect(),
+            context,
+        )
+    }
+
+    /// Parse the annotations in the doc comments of `attrs`, like `/// @Column(name = "id")`.
+    /// Annotations in attributes are not read, see `DocAnnotation`.
+    pub fn from_doc_comments(attrs: &[Attribute]) -> Result<Self, Error> {
+        Self::from_doc_comments_with_context(attrs, &AnnotationContext::default())
+    }
+
+    pub fn from_doc_comments_with_context(
+        attrs: &[Attribute],
+        context: &AnnotationContext,
+    ) -> Result<Self, Error> {
+        let matched: Vec<DocAnnotation> = DocAnnotation::from_attributes(attrs)
+            .into_iter()
+            .filter(|doc| doc.path == T::get_path())
+            .collect();
+        let attributes: Vec<T> = ErrorCollector::collect(
+            matched
+                .iter()
+                .map(|doc| parse_annotation(doc.to_meta(), context)),
+        )
+        .map_err(to_syn_error)?;
+
+        Self::from_parsed(
+            attributes,
+            matched.iter().map(|doc| doc.span).collect(),
+            matched.iter().map(|_| None).collect(),
+            context,
+        )
+    }
+
+    fn from_parsed(
+        attributes: Vec<T>,
+        spans: Vec<Span>,
+        predicates: Vec<Option<syn::Meta>>,
+        context: &AnnotationContext,
+    ) -> Result<Self, Error> {
+        let structures = AnnotationStructures {
+            attrs: attributes,
+            warnings: context.take_warnings(),
+            spans,
+            predicates,
+        };
+        match T::is_unique() {
+            true => structures.check_only_one().map(|_| structures),
+            false => Ok(structures),
+        }
+    }
+
+    /// The `cfg_attr` predicate of each parsed annotation in `attrs`, only kept with
+    /// `CfgAttrMode::Expose` in the context.
+    pub fn predicates(&self) -> &[Option<syn::Meta>] {
+        &self.predicates
+    }
+
+    /// The parsed annotations, in the order of their attributes.
+    pub fn attrs(&self) -> &[T] {
+        &self.attrs
+    }
+
+    pub fn into_attrs(self) -> Vec<T> {
+        self.attrs
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.attrs.first()
+    }
+
+    /// The annotation if it is present, an error if it appears more than once.
+    pub fn only_one(self) -> Result<Option<T>, Error> {
+        self.check_only_one()?;
+        Ok(self.attrs.into_iter().next())
+    }
+
+    /// Merge all parsed annotations into one, like the members of repeated group attributes.
+    pub fn merged(self) -> Result<Option<T>, Error> {
+        let spans = self
+            .spans
+            .into_iter()
+            .chain(std::iter::repeat(Span::call_site()));
+        let mut attrs = self.attrs.into_iter().zip(spans);
+        let mut merged = match attrs.next() {
+            Some((first, _)) => first,
+            None => return Ok(None),
+        };
+        let mut errors = ErrorCollector::new();
+        for (attr, span) in attrs {
+            errors.handle(merged.merge(attr).map_err(|e| e.or_span(span)));
+        }
+
+        errors.finish_with(Some(merged))
+    }
+
+    fn check_only_one(&self) -> Result<(), Error> {
+        match self.attrs.len() > 1 {
+            true => Err(Error::new(
+                self.spans.get(1).copied().unwrap_or_else(Span::call_site),
+                format!("Annotation `{}` may appear only once", T::get_path()),
+            )),
+            false => Ok(()),
+        }
+    }
+}
 
-        Ok(AnnotationStructures { attrs: attributes })
+/// Annotations built in code, they have no spans and no `cfg_attr` predicates.
+impl<T: AnnotationStructure> From<Vec<T>> for AnnotationStructures<T> {
+    fn from(attrs: Vec<T>) -> Self {
+        AnnotationStructures {
+            spans: attrs.iter().map(|_| Span::call_site()).collect(),
+            predicates: attrs.iter().map(|_| None).collect(),
+            attrs,
+            warnings: Vec::new(),
+        }
     }
 }
 
 impl<T: AnnotationStructure> Parse for AnnotationStructures<T> {
     fn parse(input: &ParseBuffer) -> Result<Self, Error> {
-        let derive_input = DeriveInput::parse(&input)?;
+        let derive_input = DeriveInput::parse(input)?;
         Self::from_derive_input(&derive_input)
     }
 }
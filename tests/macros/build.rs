@@ -0,0 +1,20 @@
+use annotation_rs::{AnnotationStructures, Scanner};
+use objects::attributes::{Column, Index, Table};
+use std::path::PathBuf;
+
+type Annotations = (
+    AnnotationStructures<Table>,
+    AnnotationStructures<Column>,
+    AnnotationStructures<Index>,
+);
+
+// the index of the scanner fixture, compiled by `tests/scanner_test.rs`
+fn main() {
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/../objects/scan_fixture");
+    println!("cargo:rerun-if-changed={}", fixture);
+    let index = Scanner::new(fixture).scan::<Annotations>().unwrap();
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    index
+        .write_rust(out_dir.join("annotations.rs"), "annotation_index")
+        .unwrap();
+}
@@ -1,7 +1,47 @@
 extern crate proc_macro;
 
-use derive::generate_reader;
-use objects::attributes::{Full, Simple};
+use derive::{generate_attribute, generate_reader};
+use objects::attributes::{Column, Full, Index, Numbers, Orm, Relation, Simple, Table};
 
 generate_reader!(SimpleDerive, [Simple]);
 generate_reader!(FullDerive, [Full]);
+generate_reader!(TableDerive, [Table], [Column]);
+generate_reader!(IndexDerive, [Index]);
+generate_reader!(NumbersDerive, [Numbers]);
+generate_reader!(RelationDerive, [Relation as relation]);
+generate_reader!(OrmDerive, [Orm as orm]);
+generate_reader!(DocTableDerive, [Table], [Column], doc_comments = true);
+generate_reader!(
+    table_reader,
+    [Table],
+    [Column, Index],
+    mode = attribute,
+    cfg_attr = true
+);
+generate_attribute!(table, Table, handler = table_handler);
+
+fn table_handler(table: Table, item: syn::Item) -> proc_macro2::TokenStream {
+    let ident = match annotation_rs::ItemRef::Item(&item).ident() {
+        Some(ident) => ident.clone(),
+        None => {
+            return syn::Error::new_spanned(item, "Expected a named item").to_compile_error();
+        }
+    };
+    let name = table.name;
+
+    quote::quote! {
+        #item
+
+        impl #ident {
+            pub fn table_name() -> &'static str {
+                #name
+            }
+        }
+    }
+}
+
+/// Stands in for a derive of another crate, whose helper attribute is close to `Table`.
+#[proc_macro_derive(ForeignDerive, attributes(Tables))]
+pub fn foreign_derive(_input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::new()
+}
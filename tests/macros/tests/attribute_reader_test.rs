@@ -0,0 +1,93 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::{get_annotation, get_annotations, has_annotation};
+
+use annotation_rs_test_macros::table_reader;
+use objects::attributes::{Column, Index, Table};
+
+struct UserAccount;
+
+#[table_reader]
+#[Table(name = "accounts")]
+impl UserAccount {
+    #[Column(name = "find_user")]
+    fn find(&self, id: i32) -> i32 {
+        id
+    }
+
+    #[Index(kind = "hash")]
+    #[Index]
+    const LIMIT: i32 = 10;
+
+    #[cfg_attr(all(), Column(name = "count_all"), allow(dead_code))]
+    fn count(&self) -> i32 {
+        0
+    }
+}
+
+#[table_reader]
+#[Table]
+fn handler(#[Column] user_name: String, count: i32) -> String {
+    user_name.repeat(count as usize)
+}
+
+#[table_reader]
+#[Table(name = "inner")]
+mod repository {
+    use objects::attributes::{Column, Index, Table};
+
+    #[Column(name = "save_all")]
+    pub fn save() -> i32 {
+        1
+    }
+}
+
+#[table_reader]
+#[Table(name = "users")]
+pub trait UserRepository {
+    #[Column(name = "find_by_id")]
+    fn find(&self, id: i32) -> i32 {
+        id
+    }
+
+    #[Index]
+    const LIMIT: i32;
+}
+
+impl UserRepository for UserAccount {
+    const LIMIT: i32 = 20;
+}
+
+#[test]
+pub fn test_attribute_reader() {
+    assert_eq!(UserAccount.find(UserAccount::LIMIT), 10);
+    let table: Table = get_annotation!(UserAccount, Table).unwrap();
+    assert_eq!(table.name, "accounts");
+    let column: Column = get_annotation!(UserAccount::find, Column).unwrap();
+    assert_eq!(column.name, "find_user");
+    assert!(has_annotation!(UserAccount::LIMIT, Index));
+    let indexes: Vec<Index> = get_annotations!(UserAccount::LIMIT, Index);
+    assert_eq!(indexes.len(), 2);
+    let column: Column = get_annotation!(UserAccount::count, Column).unwrap();
+    assert_eq!(column.name, "count_all");
+
+    assert_eq!(handler("a".to_string(), 2), "aa");
+    let table: Table = get_annotation!(handler, Table).unwrap();
+    assert_eq!(table.name, "handler");
+    let column: Column = get_annotation!(handler::user_name, Column).unwrap();
+    assert_eq!(column.name, "userName");
+    assert!(!has_annotation!(handler::count, Column));
+
+    assert_eq!(repository::save(), 1);
+    let table: Table = get_annotation!(repository, Table).unwrap();
+    assert_eq!(table.name, "inner");
+    let column: Column = get_annotation!(repository::save, Column).unwrap();
+    assert_eq!(column.label, "SAVE_ALL");
+
+    assert_eq!(UserRepository::find(&UserAccount, 3), 3);
+    let table: Table = get_annotation!(user_repository, Table).unwrap();
+    assert_eq!(table.name, "users");
+    let column: Column = get_annotation!(user_repository::find, Column).unwrap();
+    assert_eq!(column.name, "find_by_id");
+    assert!(has_annotation!(user_repository::LIMIT, Index));
+    assert_eq!(<UserAccount as UserRepository>::LIMIT, 20);
+}
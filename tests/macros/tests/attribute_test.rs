@@ -0,0 +1,16 @@
+use annotation_rs_test_macros::table;
+
+#[table]
+struct UserAccount;
+
+#[table(name = "users")]
+#[allow(dead_code)]
+struct User {
+    id: i32,
+}
+
+#[test]
+pub fn test_attribute_macro() {
+    assert_eq!(UserAccount::table_name(), "user_account");
+    assert_eq!(User::table_name(), "users");
+}
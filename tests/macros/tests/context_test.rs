@@ -0,0 +1,26 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::TableDerive;
+use objects::attributes::{Column, Table};
+
+#[derive(TableDerive)]
+#[Table]
+#[allow(dead_code)]
+struct UserAccount {
+    #[Column]
+    user_id: i32,
+    #[Column(name = "mail")]
+    email_address: String,
+}
+
+#[test]
+pub fn test_context() {
+    let table: Table = get_annotation!(UserAccount, Table).unwrap();
+    assert_eq!(table.name, "user_account");
+    let user_id: Column = get_annotation!(UserAccount::user_id, Column).unwrap();
+    assert_eq!(user_id.name, "userId");
+    assert_eq!(user_id.label, "USERID");
+    let email_address: Column = get_annotation!(UserAccount::email_address, Column).unwrap();
+    assert_eq!(email_address.name, "mail");
+}
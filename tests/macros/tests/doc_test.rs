@@ -0,0 +1,37 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::{get_annotation, has_annotation};
+
+use annotation_rs_test_macros::DocTableDerive;
+use objects::attributes::{Column, Table};
+
+/// A user account.
+///
+/// @Table(name = "accounts")
+#[derive(DocTableDerive)]
+#[allow(dead_code)]
+struct UserAccount {
+    /// @Column(
+    ///     name = "account_id"
+    /// )
+    id: i32,
+    #[Column(name = "user_name")]
+    name: String,
+    /// The age, @Column is not read here.
+    /// @Column is not read at the start of prose either.
+    ///
+    /// ```
+    /// @Column(name = "example")
+    /// ```
+    age: i32,
+}
+
+#[test]
+pub fn test_doc_comments() {
+    let table: Table = get_annotation!(UserAccount, Table).unwrap();
+    assert_eq!(table.name, "accounts");
+    let column: Column = get_annotation!(UserAccount::id, Column).unwrap();
+    assert_eq!(column.name, "account_id");
+    let column: Column = get_annotation!(UserAccount::name, Column).unwrap();
+    assert_eq!(column.name, "user_name");
+    assert!(!has_annotation!(UserAccount::age, Column));
+}
@@ -0,0 +1,19 @@
+#![feature(proc_macro_hygiene)]
+// `Tables` looks like a misspelled `Table`, which is reported as a warning.
+#![allow(deprecated)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::{ForeignDerive, TableDerive};
+use objects::attributes::Table;
+
+#[derive(TableDerive, ForeignDerive)]
+#[Table(name = "accounts")]
+#[Tables(name = "other")]
+#[Tables(not => the, grammar of annotations)]
+struct Account;
+
+#[test]
+pub fn test_foreign_attributes() {
+    let table: Table = get_annotation!(Account, Table).unwrap();
+    assert_eq!(table.name, "accounts");
+}
@@ -0,0 +1,27 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::SimpleDerive;
+use objects::attributes::Simple;
+use objects::enums::TestEnum;
+
+#[derive(SimpleDerive)]
+#[Simple(
+    i32 = -8,
+    u16 = 60 * 5,
+    float = -0.5,
+    string = crate::handler,
+    enum2 => "variant_b"
+)]
+#[allow(dead_code)]
+struct Handler;
+
+#[test]
+pub fn test_grammar() {
+    let simple: Simple = get_annotation!(Handler, Simple).unwrap();
+    assert_eq!(simple.int32, -8);
+    assert_eq!(simple.unsigned16, 300);
+    assert_eq!(simple.float, -0.5);
+    assert_eq!(simple.string, "crate::handler");
+    assert_eq!(simple.enum2, TestEnum::VariantB);
+}
@@ -0,0 +1,29 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::OrmDerive;
+use objects::attributes::Orm;
+
+#[derive(OrmDerive)]
+#[orm(table(name = "users"), index(kind = "hash"))]
+#[orm(index())]
+#[allow(dead_code)]
+struct User;
+
+#[derive(OrmDerive)]
+#[orm(index())]
+#[allow(dead_code)]
+struct UserProfile;
+
+#[test]
+pub fn test_group() {
+    let orm: Orm = get_annotation!(User, Orm).unwrap();
+    assert_eq!(orm.table.unwrap().name, "users");
+    assert_eq!(orm.index.len(), 2);
+    assert_eq!(orm.index[0].kind, "hash");
+    assert_eq!(orm.index[1].kind, "btree");
+
+    let orm: Orm = get_annotation!(UserProfile, Orm).unwrap();
+    assert!(orm.table.is_none());
+    assert_eq!(orm.index.len(), 1);
+}
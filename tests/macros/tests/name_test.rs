@@ -0,0 +1,16 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::RelationDerive;
+use objects::attributes::Relation;
+
+#[derive(RelationDerive)]
+#[relation(target = "user")]
+#[allow(dead_code)]
+struct Post;
+
+#[test]
+pub fn test_renamed_annotation() {
+    let relation: Relation = get_annotation!(Post, Relation).unwrap();
+    assert_eq!(relation.target, "user");
+}
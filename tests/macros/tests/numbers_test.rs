@@ -0,0 +1,21 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::NumbersDerive;
+use objects::attributes::Numbers;
+
+#[derive(NumbersDerive)]
+#[Numbers(size = 1, offset = 2, letter = 'x', count = 3, ratio = 4)]
+#[allow(dead_code)]
+struct Buffer;
+
+#[test]
+pub fn test_numbers() {
+    let numbers: Numbers = get_annotation!(Buffer, Numbers).unwrap();
+    assert_eq!(numbers.size, 1);
+    assert_eq!(numbers.offset, 2);
+    assert_eq!(numbers.letter, 'x');
+    assert_eq!(numbers.count.get(), 3);
+    assert_eq!(numbers.limit.map(|limit| limit.get()), Some(5));
+    assert_eq!(numbers.ratio, 4.0);
+}
@@ -0,0 +1,47 @@
+#![feature(proc_macro_hygiene)]
+use annotation_rs::{get_annotation, get_annotations};
+
+use annotation_rs_test_macros::{IndexDerive, TableDerive};
+use objects::attributes::{Index, Table};
+
+#[derive(IndexDerive)]
+#[Index(kind = "hash")]
+#[Index]
+#[allow(dead_code)]
+struct Account {
+    id: i32,
+}
+
+#[derive(IndexDerive)]
+#[allow(dead_code)]
+struct Session;
+
+#[derive(TableDerive)]
+#[Table(name = "ledgers")]
+#[allow(dead_code)]
+struct Ledger;
+
+#[derive(TableDerive)]
+#[allow(dead_code)]
+struct Draft;
+
+#[test]
+pub fn test_repeatable() {
+    let indexes: Vec<Index> = get_annotations!(Account, Index);
+    assert_eq!(
+        indexes
+            .iter()
+            .map(|index| index.kind.as_str())
+            .collect::<Vec<_>>(),
+        vec!["hash", "btree"]
+    );
+    let index: Index = get_annotation!(Account, Index).unwrap();
+    assert_eq!(index.kind, "hash");
+
+    assert!(get_annotations!(Session, Index).is_empty());
+
+    let tables: Vec<Table> = get_annotations!(Ledger, Table);
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].name, "ledgers");
+    assert!(get_annotations!(Draft, Table).is_empty());
+}
@@ -0,0 +1,18 @@
+include!(concat!(env!("OUT_DIR"), "/annotations.rs"));
+
+#[test]
+pub fn test_scanner_index() {
+    let index = annotation_index();
+    assert_eq!(index.len(), 8);
+    let (path, (tables, columns, indexes)) = &index[0];
+    assert_eq!(*path, "crate::api::users::User");
+    assert_eq!(tables[0].name, "users");
+    assert!(columns.is_empty() && indexes.is_empty());
+
+    let (_, (_, columns, _)) = index
+        .iter()
+        .find(|(path, _)| *path == "crate::api::users::User::user_name")
+        .unwrap();
+    assert_eq!(columns[0].name, "userName");
+    assert_eq!(columns[0].label, "USERNAME");
+}
@@ -0,0 +1,9 @@
+#![deny(deprecated)]
+use annotation_rs_test_macros::IndexDerive;
+use objects::attributes::Index;
+
+#[derive(IndexDerive)]
+#[Index(kind = "btree", method = "hash")]
+struct Account;
+
+fn main() {}
@@ -0,0 +1,10 @@
+#![deny(deprecated)]
+use annotation_rs_test_macros::{ForeignDerive, TableDerive};
+use objects::attributes::Table;
+
+#[derive(TableDerive, ForeignDerive)]
+#[Table(name = "accounts")]
+#[Tables(name = "other")]
+struct Account;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use annotation_rs_test_macros::table_reader;
+use objects::attributes::Table;
+
+struct UserAccount;
+
+#[table_reader(strict)]
+#[Table(name = "accounts")]
+impl UserAccount {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use annotation_rs_test_macros::table_reader;
+use objects::attributes::{Column, Index, Table};
+
+struct UserAccount;
+
+#[table_reader]
+#[Table(name = "accounts")]
+impl UserAccount {
+    fn find(&self, #[Column] id: i32) -> i32 {
+        id
+    }
+}
+
+fn main() {}
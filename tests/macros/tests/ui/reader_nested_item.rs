@@ -0,0 +1,15 @@
+use annotation_rs_test_macros::table_reader;
+use objects::attributes::{Column, Index, Table};
+
+#[table_reader]
+#[Table(name = "accounts")]
+mod accounts {
+    use super::*;
+
+    mod queries {
+        #[Column]
+        pub fn find() {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use annotation_rs_test_macros::table_reader;
+use objects::attributes::{Column, Index, Table};
+
+#[table_reader]
+#[Table(name = "users")]
+trait UserRepository {
+    fn find(&self, #[Column] id: i32) -> i32;
+}
+
+fn main() {}
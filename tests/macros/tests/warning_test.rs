@@ -0,0 +1,22 @@
+#![feature(proc_macro_hygiene)]
+// The annotation below reports warnings through deprecated constants on purpose, they are
+// checked by `tests/ui/deprecated_field.rs`.
+#![allow(deprecated)]
+use annotation_rs::get_annotation;
+
+use annotation_rs_test_macros::IndexDerive;
+use objects::attributes::Index;
+
+#[derive(IndexDerive)]
+#[Index(kind = "btree", method = "hash")]
+#[allow(dead_code)]
+struct Account {
+    id: i32,
+}
+
+#[test]
+pub fn test_warning() {
+    let index: Index = get_annotation!(Account, Index).unwrap();
+    assert_eq!(index.kind, "btree");
+    assert_eq!(index.method, Some(String::from("hash")));
+}
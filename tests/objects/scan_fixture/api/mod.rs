@@ -0,0 +1,6 @@
+pub mod users;
+
+pub mod admin {
+    #[Table(name = "admins")]
+    pub struct Admin;
+}
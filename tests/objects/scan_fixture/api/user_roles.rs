@@ -0,0 +1,2 @@
+#[Table(name = "user_roles")]
+pub struct UserRole;
@@ -0,0 +1,18 @@
+mod get;
+
+#[Table(name = "users")]
+pub struct User {
+    #[Column]
+    pub user_name: String,
+    pub age: u8,
+}
+
+impl User {
+    #[Index]
+    pub fn find(id: u64) -> Option<User> {
+        None
+    }
+}
+
+#[path = "user_roles.rs"]
+mod roles;
@@ -0,0 +1,2 @@
+#[cfg_attr(feature = "db", Index(kind = "hash"))]
+pub fn get() {}
@@ -0,0 +1,5 @@
+pub enum Role {
+    #[Column(name = "ADMIN")]
+    Admin,
+    Guest,
+}
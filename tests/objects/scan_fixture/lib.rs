@@ -0,0 +1,10 @@
+mod api;
+#[path = "extra/models.rs"]
+mod models;
+
+#[Table]
+#[Index(kind = "hash")]
+pub struct Session {
+    #[Column(name = "sessionId")]
+    id: u64,
+}
@@ -1,7 +1,8 @@
 use crate::enums::TestEnum;
 use std::collections::HashMap;
+use std::num::{NonZeroU32, NonZeroU8};
 
-use derive::Annotation;
+use derive::{Annotation, AnnotationGroup};
 
 #[derive(Annotation, Clone)]
 #[mod_path = "objects::attributes"]
@@ -37,3 +38,1205 @@ pub struct Full {
     #[field(enum_value = true)]
     pub map3: HashMap<String, Vec<TestEnum>>,
 }
+
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+pub struct Numbers {
+    pub size: usize,
+    pub offset: isize,
+    pub letter: char,
+    pub count: NonZeroU32,
+    #[field(default = 5)]
+    pub limit: Option<NonZeroU8>,
+    pub ratio: f64,
+}
+
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+#[annotation(name = "orm::relation")]
+pub struct Relation {
+    pub target: String,
+}
+
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+#[annotation(unique)]
+pub struct Table {
+    #[field(default_from = "item_name", case = "snake")]
+    pub name: String,
+}
+
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+#[annotation(repeatable)]
+pub struct Index {
+    #[field(default = "btree")]
+    pub kind: String,
+    #[field(deprecated = "use `kind` instead")]
+    pub method: Option<String>,
+}
+
+#[derive(AnnotationGroup, Clone)]
+#[mod_path = "objects::attributes"]
+#[annotation(name = "orm")]
+pub struct Orm {
+    pub table: Option<Table>,
+    pub index: Vec<Index>,
+}
+
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+pub struct Column {
+    #[field(default_from = "field_name", case = "camel")]
+    pub name: String,
+    #[field(default_from_field = "name")]
+    pub alias: Option<String>,
+    #[field(default_from_field = "alias", default_with = "str::to_uppercase")]
+    pub label: String,
+}
+
+/// Fields named like the locals of the generated parser.
+#[derive(Annotation, Clone)]
+#[mod_path = "objects::attributes"]
+pub struct Locals {
+    pub errors: Vec<String>,
+    pub span: String,
+    pub missing_fields: bool,
+    pub invalid_fields: Option<i32>,
+    #[field(default_from = "item_name")]
+    pub context: String,
+}
+
+/// Members named like the locals of the generated group parser.
+#[derive(AnnotationGroup, Clone)]
+#[mod_path = "objects::attributes"]
+#[annotation(name = "locals")]
+pub struct LocalGroup {
+    pub errors: Option<Table>,
+    pub context: Vec<Index>,
+    pub tokens: Option<Tuple>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use annotation_rs::{
+        meta, AnnotatedMember, AnnotationContext, AnnotationStructure, AnnotationStructures,
+        CfgAttrMode, CfgSet, Error, ErrorKind, Member, Symbol, Value,
+    };
+    use proc_macro2::Span;
+    use quote::ToTokens;
+
+    /// The arguments of `meta`, for the entry points of `AnnotationStructure` which take a context.
+    fn args(meta: &syn::Meta) -> Vec<meta::NestedMeta> {
+        meta::Meta::from(meta.clone()).nested().unwrap().to_vec()
+    }
+
+    /// Parse `meta` strictly, any error fails the test.
+    fn parse<T: AnnotationStructure>(meta: &syn::Meta, context: &AnnotationContext) -> T {
+        match T::parse_nested_meta(&args(meta), context, Span::call_site()) {
+            Ok(value) => value,
+            Err(errors) => panic!("unexpected parse errors: {:?}", messages(&errors)),
+        }
+    }
+
+    /// Parse `meta`, which must fail, and return its errors.
+    fn parse_errors<T: AnnotationStructure>(meta: &syn::Meta) -> Vec<Error> {
+        match T::parse_nested_meta(
+            &args(meta),
+            &AnnotationContext::default(),
+            Span::call_site(),
+        ) {
+            Ok(_) => panic!("expected parse errors"),
+            Err(errors) => errors,
+        }
+    }
+
+    fn messages(errors: &[Error]) -> Vec<String> {
+        errors.iter().map(|error| error.to_string()).collect()
+    }
+
+    /// Defaults taken from the context and from other fields.
+    mod defaults {
+        use super::*;
+
+        #[test]
+        fn test_context_default() {
+            let derive_input: syn::DeriveInput = syn::parse_quote! {
+                #[Table]
+                struct UserAccount;
+            };
+            let tables = AnnotationStructures::<Table>::from_derive_input(&derive_input).unwrap();
+            assert_eq!(tables.attrs[0].name, "user_account");
+
+            let meta: syn::Meta = syn::parse_quote!(Table(name = "accounts"));
+            let context = AnnotationContext::from_derive_input(&derive_input);
+            let table = parse::<Table>(&meta, &context);
+            assert_eq!(table.name, "accounts");
+
+            let derive_input: syn::DeriveInput = syn::parse_quote! {
+                struct Wrapper<'a, T: Clone, const N: usize>(&'a [T; N]);
+            };
+            let context = AnnotationContext::from_derive_input(&derive_input);
+            assert_eq!(
+                quote::ToTokens::to_token_stream(&context.enclosing_type.unwrap()).to_string(),
+                "Wrapper < 'a , T , N >"
+            );
+
+            let meta: syn::Meta = syn::parse_quote!(Table);
+            assert!(Table::from_meta(&meta).is_err());
+        }
+
+        #[test]
+        fn test_field_default() {
+            let meta: syn::Meta = syn::parse_quote!(Column(name = "user_id"));
+            let column = Column::from_meta(&meta).unwrap();
+            assert_eq!(column.alias, Some(String::from("user_id")));
+            assert_eq!(column.label, "USER_ID");
+
+            let meta: syn::Meta = syn::parse_quote!(Column(name = "user_id", alias = "id"));
+            let column = Column::from_meta(&meta).unwrap();
+            assert_eq!(column.alias, Some(String::from("id")));
+            assert_eq!(column.label, "ID");
+        }
+    }
+
+    /// Accumulated, structured and lenient errors, and warnings.
+    mod errors {
+        use super::*;
+
+        #[test]
+        fn test_error_accumulation() {
+            let meta: syn::Meta = syn::parse_quote!(Simple(i32 = "x", u16 = 2, unknown = 1));
+            let error = match Simple::from_meta(&meta) {
+                Ok(_) => panic!("expected parse errors"),
+                Err(error) => error,
+            };
+            // invalid i32, unknown key, and missing float / string / enum2
+            assert_eq!(error.into_iter().count(), 3);
+
+            let meta: syn::Meta = syn::parse_quote!(Full(vector(1, 2), map(a = "x", b = "y")));
+            let error = match Full::from_meta(&meta) {
+                Ok(_) => panic!("expected parse errors"),
+                Err(error) => error,
+            };
+            // two invalid list items, two invalid enum values and missing object / map2 / map3
+            assert_eq!(error.into_iter().count(), 5);
+        }
+
+        #[test]
+        fn test_structured_error() {
+            let meta: syn::Meta = syn::parse_quote!(Full(
+                object(
+                    i32 = 1,
+                    u16 = 2,
+                    float = 1.1,
+                    string = "x",
+                    enum2 = "variant_d"
+                ),
+                vector("a"),
+                map(a = 1),
+                map2(),
+                map3()
+            ));
+            let errors = parse_errors::<Full>(&meta);
+            assert_eq!(errors.len(), 2);
+
+            assert_eq!(errors[0].path(), ["object", "enum2"]);
+            match errors[0].kind() {
+                ErrorKind::InvalidEnumValue {
+                    enum_name, value, ..
+                } => assert_eq!(
+                    (enum_name.as_str(), value.as_str()),
+                    ("TestEnum", "variant_d")
+                ),
+                other => panic!("unexpected error kind: {:?}", other),
+            }
+
+            assert_eq!(errors[1].path_string(), "map.a");
+            assert_eq!(
+                errors[1].kind(),
+                &ErrorKind::TypeMismatch {
+                    expected: String::from("string")
+                }
+            );
+            assert_eq!(errors[1].get_message(), "expected map.a lit to be a string");
+
+            // without a path, like the error of a top-level value
+            assert_eq!(
+                Error::type_mismatch("string").to_string(),
+                "expected lit to be a string"
+            );
+        }
+
+        #[test]
+        fn test_missing_fields_error() {
+            let meta: syn::Meta = syn::parse_quote!(Simple(u16 = 2, enum2 = "aaa"));
+            assert_eq!(
+                messages(&parse_errors::<Simple>(&meta)),
+                vec![
+                    "Missing required key `i32` (field `int32`) in annotation `Simple`, \
+                     other missing required keys: `float`, `string`"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_suggestion() {
+            let meta: syn::Meta = syn::parse_quote!(Simple(
+                int32 = 1,
+                u16 = 2,
+                flaot = 1.1,
+                string = "test",
+                enum2 = "aaa"
+            ));
+            let messages = messages(&parse_errors::<Simple>(&meta));
+            assert!(messages.contains(&String::from(
+                "Unexpected key `int32`\n\nhelp: did you mean `i32`?"
+            )));
+            assert!(messages.contains(&String::from(
+                "Unexpected key `flaot`\n\nhelp: did you mean `float`?"
+            )));
+
+            let path: syn::Path = syn::parse_quote!(Simpel);
+            let known = [("Simple", "Simple"), ("Full", "Full")];
+            assert!(annotation_rs::unknown_annotation_error(&path, &known).is_some());
+            let path: syn::Path = syn::parse_quote!(derive);
+            assert!(annotation_rs::unknown_annotation_error(&path, &known).is_none());
+        }
+
+        #[test]
+        fn test_lenient() {
+            let context = AnnotationContext::default();
+            let meta: syn::Meta = syn::parse_quote!(Simple(
+                i32 = "x",
+                u16 = 2,
+                string = "test",
+                enum2 = "aaa",
+                unknown = 1
+            ));
+            let (simple, errors) =
+                Simple::from_nested_meta_lenient(&args(&meta), &context, Span::call_site());
+            let simple = simple.unwrap();
+            assert_eq!(simple.int32, 0);
+            assert_eq!(simple.unsigned16, 2);
+            assert_eq!(simple.float, 0.0);
+            assert_eq!(simple.string, "test");
+            assert_eq!(simple.enum1, Some(TestEnum::VariantC));
+            assert_eq!(simple.enum2, TestEnum::VariantA);
+            // invalid i32, unknown key and missing float
+            assert_eq!(errors.len(), 3);
+
+            // required enum without a valid value can not be filled
+            let meta: syn::Meta = syn::parse_quote!(Simple(i32 = 1, enum2 = "unknown"));
+            let (simple, errors) =
+                Simple::from_nested_meta_lenient(&args(&meta), &context, Span::call_site());
+            assert!(simple.is_none());
+            assert_eq!(errors.len(), 2);
+        }
+
+        #[test]
+        fn test_warnings() {
+            let meta: syn::Meta = syn::parse_quote!(Index(kind = "btree", method = "hash"));
+            let context = AnnotationContext::default();
+            parse::<Index>(&meta, &context);
+            let warnings: Vec<String> = context
+                .take_warnings()
+                .iter()
+                .map(|warning| warning.to_string())
+                .collect();
+            assert_eq!(
+                warnings,
+                vec![
+                    "Redundant value for `kind`, it is the same as the default",
+                    "`method` is deprecated: use `kind` instead"
+                ]
+            );
+            assert!(context.warnings().is_empty());
+
+            let derive_input: syn::DeriveInput = syn::parse_quote! {
+                #[Index(kind = "hash")]
+                struct Account;
+            };
+            let annotations =
+                AnnotationStructures::<Index>::from_derive_input(&derive_input).unwrap();
+            assert_eq!(annotations.attrs.len(), 1);
+            assert!(annotations.warnings.is_empty());
+        }
+    }
+
+    /// Literal types and the token grammar of `annotation_rs::meta`.
+    mod values {
+        use super::*;
+
+        #[test]
+        fn test_numbers() {
+            let meta: syn::Meta = syn::parse_quote!(Numbers(
+                size = 1usize,
+                offset = 2,
+                letter = 'a',
+                count = 3u32,
+                ratio = 1
+            ));
+            let numbers = Numbers::from_meta(&meta).unwrap();
+            assert_eq!(numbers.size, 1);
+            assert_eq!(numbers.offset, 2);
+            assert_eq!(numbers.letter, 'a');
+            assert_eq!(numbers.count.get(), 3);
+            assert_eq!(numbers.limit.map(NonZeroU8::get), Some(5));
+            assert_eq!(numbers.ratio, 1.0);
+
+            let meta: syn::Meta = syn::parse_quote!(Numbers(
+                size = 1,
+                offset = 2,
+                letter = 'a',
+                count = 0,
+                limit = 256,
+                ratio = 1e400
+            ));
+            let errors = parse_errors::<Numbers>(&meta);
+            let messages: Vec<String> = messages(&errors);
+            assert_eq!(
+                messages,
+                vec![
+                    "`count`: 0 is not allowed for NonZeroU32",
+                    "256 is too large for NonZeroU8 at `limit`",
+                    "1e400 is too large for f64 at `ratio`"
+                ]
+            );
+            assert_eq!(
+                errors[0].kind(),
+                &ErrorKind::Validation(String::from("0 is not allowed for NonZeroU32"))
+            );
+
+            let meta: syn::Meta = syn::parse_quote!(Simple(
+                i32 = 1,
+                u16 = 70000,
+                float = 1.0f64,
+                string = "",
+                enum2 = "aaa"
+            ));
+            let errors = parse_errors::<Simple>(&meta);
+            assert_eq!(
+                errors[0].kind(),
+                &ErrorKind::Overflow {
+                    value: String::from("70000"),
+                    ty: String::from("u16")
+                }
+            );
+            assert_eq!(
+                errors[1].kind(),
+                &ErrorKind::SuffixMismatch {
+                    expected: String::from("f32"),
+                    found: String::from("f64")
+                }
+            );
+        }
+
+        #[test]
+        fn test_token_grammar() {
+            let context = AnnotationContext::default();
+            let nested = meta::parse_args(quote::quote!(
+                i32 = -1,
+                u16 => 60 * 5,
+                float = -1.5,
+                string = crate::handler,
+                enum2 = "aaa"
+            ))
+            .unwrap();
+            let simple = Simple::parse_nested_meta(&nested, &context, Span::call_site())
+                .ok()
+                .unwrap();
+            assert_eq!(simple.int32, -1);
+            assert_eq!(simple.unsigned16, 300);
+            assert_eq!(simple.float, -1.5);
+            assert_eq!(simple.string, "crate::handler");
+
+            let nested = meta::parse_args(quote::quote!(
+                object(i32 = 1, u16 = 2, float = 1.0, string = "", enum2 = "aaa"),
+                vector(crate::a, "b"),
+                map("content-type" => "aaa", accept = "variant_b"),
+                map2(),
+                map3()
+            ))
+            .unwrap();
+            let full = Full::parse_nested_meta(&nested, &context, Span::call_site())
+                .ok()
+                .unwrap();
+            assert_eq!(full.vector, vec!["crate::a", "b"]);
+            assert_eq!(full.map.get("content-type"), Some(&TestEnum::VariantA));
+            assert_eq!(full.map.get("accept"), Some(&TestEnum::VariantB));
+
+            let nested = meta::parse_args(quote::quote!(
+                i32 = 1,
+                u16 = -1,
+                float = 1.0,
+                string = "",
+                enum2 = "aaa"
+            ))
+            .unwrap();
+            let errors = Simple::parse_nested_meta(&nested, &context, Span::call_site())
+                .err()
+                .unwrap();
+            assert_eq!(
+                errors[0].kind(),
+                &ErrorKind::Underflow {
+                    value: String::from("-1"),
+                    ty: String::from("u16")
+                }
+            );
+            let errors = annotation_rs::Error::from_syn(
+                meta::parse_args(quote::quote!(i32 = , u16 = 1))
+                    .err()
+                    .unwrap(),
+            );
+            assert!(matches!(errors[0].kind(), ErrorKind::Syntax(_)));
+            let source = std::error::Error::source(&errors[0]).unwrap();
+            assert_eq!(
+                source.downcast_ref::<syn::Error>().unwrap().to_string(),
+                errors[0].to_string()
+            );
+        }
+    }
+
+    /// Annotation paths, and fields named like the locals of generated parsers.
+    mod names {
+        use super::*;
+
+        #[test]
+        fn test_annotation_name() {
+            let path = Relation::get_path();
+            assert_eq!(path.to_string(), "orm::relation");
+            assert_eq!(path.name(), "relation");
+            assert!(syn::parse_str::<syn::Path>("orm::relation").unwrap() == path);
+            assert!(syn::parse_str::<syn::Path>("relation").unwrap() == path);
+            assert!(syn::parse_str::<syn::Path>("other::relation").unwrap() != path);
+            assert!(syn::parse_str::<syn::Path>("Relation").unwrap() != path);
+            assert_eq!(Relation::PATH, Some("orm::relation"));
+            assert!(annotation_rs::symbol::is_name_of(
+                "orm::relation",
+                "relation"
+            ));
+            assert!(annotation_rs::symbol::is_name_of("relation", "relation"));
+            assert!(!annotation_rs::symbol::is_name_of(
+                "orm::relation",
+                "Relation"
+            ));
+            assert!(!annotation_rs::symbol::is_name_of("orm::relation", "tion"));
+            assert!(!annotation_rs::symbol::is_name_of("orm", "orm::relation"));
+
+            let attribute: syn::Attribute = syn::parse_quote!(#[orm::relation(target = "user")]);
+            let relation = AnnotationStructures::<Relation>::from_attributes(&[attribute])
+                .unwrap()
+                .only_one()
+                .unwrap()
+                .unwrap();
+            assert_eq!(relation.target, "user");
+        }
+
+        #[test]
+        fn test_local_names() {
+            let meta: syn::Meta = syn::parse_quote!(Locals(
+                errors("a", "b"),
+                span = "s",
+                missing_fields = true,
+                invalid_fields = 1
+            ));
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                struct Item;
+            );
+            let context = AnnotationContext::from_derive_input(&derive_input);
+            let locals = parse::<Locals>(&meta, &context);
+            assert_eq!(locals.errors, vec!["a", "b"]);
+            assert_eq!(locals.span, "s");
+            assert_eq!(locals.context, "Item");
+            assert!(locals.missing_fields);
+            assert_eq!(locals.invalid_fields, Some(1));
+            assert_eq!(
+                quote::ToTokens::to_token_stream(&locals).to_string(),
+                quote::quote!(objects::attributes::Locals {
+                    errors: vec![String::from("a"), String::from("b")],
+                    span: String::from("s"),
+                    missing_fields: true,
+                    invalid_fields: Some(1i32),
+                    context: String::from("Item")
+                })
+                .to_string()
+            );
+
+            let meta: syn::Meta = syn::parse_quote!(Locals(span = "s", invalid_fields = "x"));
+            let errors = parse_errors::<Locals>(&meta);
+            assert_eq!(errors.len(), 2);
+            assert_eq!(
+                errors[1].to_string(),
+                "Missing required key `errors` in annotation `Locals`, other missing required keys: `missing_fields`, `context`"
+            );
+        }
+
+        #[test]
+        fn test_group_local_names() {
+            let meta: syn::Meta = syn::parse_quote!(locals(
+                errors(name = "users"),
+                context(kind = "hash"),
+                tokens("a")
+            ));
+            let group = LocalGroup::from_meta(&meta).unwrap();
+            assert_eq!(group.errors.as_ref().unwrap().name, "users");
+            assert_eq!(group.context[0].kind, "hash");
+            assert_eq!(group.tokens.as_ref().unwrap().0, Some(String::from("a")));
+            assert!(quote::ToTokens::to_token_stream(&group)
+                .to_string()
+                .starts_with("objects :: attributes :: LocalGroup { errors : Some"));
+        }
+    }
+
+    /// Annotation groups, repeatable and unique annotations.
+    mod groups {
+        use super::*;
+
+        #[test]
+        fn test_group() {
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[orm(table(name = "users"), index(kind = "hash"))]
+                #[orm(index())]
+                struct User;
+            );
+            let orm = AnnotationStructures::<Orm>::from_derive_input(&derive_input)
+                .unwrap()
+                .merged()
+                .unwrap()
+                .unwrap();
+            assert_eq!(orm.table.unwrap().name, "users");
+            assert_eq!(
+                orm.index
+                    .iter()
+                    .map(|index| index.kind.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["hash", "btree"]
+            );
+
+            let meta: syn::Meta = syn::parse_quote!(orm(table(name = "users"), tabel()));
+            let errors = parse_errors::<Orm>(&meta);
+            assert_eq!(
+                errors[0].kind(),
+                &ErrorKind::UnknownKey {
+                    key: String::from("tabel"),
+                    suggestion: Some(String::from("table"))
+                }
+            );
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[orm(table(name = "a"))]
+                #[orm(table(name = "b"))]
+                struct User;
+            );
+            let error = AnnotationStructures::<Orm>::from_derive_input(&derive_input)
+                .unwrap()
+                .merged()
+                .err()
+                .unwrap();
+            assert_eq!(
+                error.to_string(),
+                "`table`: Annotation `Table` may appear only once"
+            );
+        }
+
+        #[test]
+        fn test_repeatable() {
+            assert!(Index::is_repeatable());
+            assert!(Table::is_unique());
+            assert!(!Simple::is_repeatable() && !Simple::is_unique());
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[Index(kind = "hash")]
+                #[Index]
+                struct User;
+            );
+            let indexes = AnnotationStructures::<Index>::from_derive_input(&derive_input).unwrap();
+            assert_eq!(indexes.attrs.len(), 2);
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[Table(name = "a")]
+                #[Table(name = "b")]
+                struct User;
+            );
+            let error = AnnotationStructures::<Table>::from_derive_input(&derive_input)
+                .err()
+                .unwrap();
+            assert_eq!(error.to_string(), "Annotation `Table` may appear only once");
+
+            // the error points at the second `Table`, not at the second attribute
+            let derive_input: syn::DeriveInput = syn::parse_str(
+                "#[derive(Clone)]\n#[Table(name = \"a\")]\n#[serde(default)]\n#[Table(name = \"b\")]\nstruct User;",
+            )
+            .unwrap();
+            let error = AnnotationStructures::<Table>::from_derive_input(&derive_input)
+                .err()
+                .unwrap();
+            assert_eq!(error.span().start().line, 4);
+        }
+    }
+
+    /// `AnnotationStructures` read from attributes, doc comments and `cfg_attr`.
+    mod structures {
+        use super::*;
+
+        #[test]
+        fn test_from_attributes() {
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                /// A user.
+                #[derive(Clone)]
+                #[serde(rename_all = "camelCase")]
+                #[Table(name = "users")]
+                struct User {
+                    #[serde(default)]
+                    #[Column]
+                    #[Column(alias = "user_name")]
+                    name: String,
+                }
+            );
+            let tables = AnnotationStructures::<Table>::from_derive_input(&derive_input).unwrap();
+            assert_eq!(tables.first().unwrap().name, "users");
+            assert_eq!(tables.only_one().unwrap().unwrap().name, "users");
+
+            let field = match &derive_input.data {
+                syn::Data::Struct(data_struct) => data_struct.fields.iter().next().unwrap(),
+                _ => unreachable!(),
+            };
+            let columns = AnnotationStructures::<Column>::from_attributes_with_context(
+                &field.attrs,
+                &AnnotationContext::from_derive_input(&derive_input).with_field(field),
+            )
+            .unwrap();
+            assert_eq!(columns.attrs().len(), 2);
+            assert_eq!(
+                columns.only_one().err().unwrap().to_string(),
+                "Annotation `Column` may appear only once"
+            );
+
+            let simple = AnnotationStructures::<Simple>::from_attributes(&field.attrs).unwrap();
+            assert!(simple.first().is_none());
+
+            let columns = AnnotationStructures::<Column>::from_attributes_with_context(
+                &field.attrs,
+                &AnnotationContext::from_derive_input(&derive_input).with_field(field),
+            )
+            .unwrap();
+            let columns = AnnotationStructures::from(columns.into_attrs());
+            assert_eq!(columns.attrs().len(), 2);
+            assert_eq!(columns.predicates().len(), 2);
+
+            let mut tables =
+                AnnotationStructures::<Table>::from_derive_input(&derive_input).unwrap();
+            let table = tables.attrs[0].clone();
+            tables.attrs.push(table);
+            assert_eq!(
+                tables.only_one().err().unwrap().to_string(),
+                "Annotation `Table` may appear only once"
+            );
+        }
+
+        #[test]
+        fn test_doc_comments() {
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                /// A user, see @Table.
+                ///
+                /// @Table(name = "users")
+                /// @Index(
+                ///     kind = "hash (unique)"
+                /// )
+                /// @Index
+                /// @param id the id
+                /// @Table is deprecated, @Table(name = "ignored") is prose too.
+                ///
+                /// ```
+                /// @Table(name = "example")
+                /// @Index(kind =
+                /// ```
+                #[Index(kind = "attribute")]
+                struct User;
+            );
+            let context = AnnotationContext::from_derive_input(&derive_input);
+            let tables = AnnotationStructures::<Table>::from_doc_comments_with_context(
+                &derive_input.attrs,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(tables.first().unwrap().name, "users");
+            assert_eq!(tables.attrs().len(), 1);
+            let indexes =
+                AnnotationStructures::<Index>::from_doc_comments(&derive_input.attrs).unwrap();
+            assert_eq!(
+                indexes
+                    .attrs()
+                    .iter()
+                    .map(|index| index.kind.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["hash (unique)", "btree"]
+            );
+            let indexes =
+                AnnotationStructures::<Index>::from_attributes(&derive_input.attrs).unwrap();
+            assert_eq!(indexes.attrs.len(), 1);
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                /// @Table(nmae = "users")
+                struct User;
+            );
+            let error = AnnotationStructures::<Table>::from_doc_comments(&derive_input.attrs)
+                .err()
+                .unwrap();
+            assert_eq!(
+                error.to_string(),
+                "Unexpected key `nmae`\n\nhelp: did you mean `name`?"
+            );
+        }
+
+        #[test]
+        fn test_cfg_attr() {
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[cfg_attr(feature = "orm", Table(name = "users"), derive(Clone))]
+                #[cfg_attr(unix, cfg_attr(not(test), Index(kind = "hash")))]
+                #[Index]
+                struct User;
+            );
+            let attrs = &derive_input.attrs;
+            let tables = AnnotationStructures::<Table>::from_attributes(attrs).unwrap();
+            assert!(tables.first().is_none());
+
+            let set = CfgSet::new().with_value("feature", "orm").with("unix");
+            let context = AnnotationContext::default().with_cfg_attr(CfgAttrMode::Evaluate(set));
+            let tables =
+                AnnotationStructures::<Table>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            assert_eq!(tables.first().unwrap().name, "users");
+            let indexes =
+                AnnotationStructures::<Index>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            assert_eq!(indexes.attrs.len(), 2);
+            assert_eq!(indexes.first().unwrap().kind, "hash");
+
+            let set = CfgSet::new().with("unix").with("test");
+            let context = AnnotationContext::default().with_cfg_attr(CfgAttrMode::Evaluate(set));
+            let tables =
+                AnnotationStructures::<Table>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            assert!(tables.first().is_none());
+            let indexes =
+                AnnotationStructures::<Index>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            assert_eq!(indexes.attrs.len(), 1);
+
+            let context = AnnotationContext::default().with_cfg_attr(CfgAttrMode::AssumeTrue);
+            let indexes =
+                AnnotationStructures::<Index>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            assert_eq!(indexes.attrs.len(), 2);
+            assert!(indexes.predicates().iter().all(Option::is_none));
+
+            let context = AnnotationContext::default().with_cfg_attr(CfgAttrMode::Expose);
+            let indexes =
+                AnnotationStructures::<Index>::from_attributes_with_context(attrs, &context)
+                    .unwrap();
+            let predicates: Vec<Option<String>> = indexes
+                .predicates()
+                .iter()
+                .map(|predicate| {
+                    predicate
+                        .as_ref()
+                        .map(|predicate| quote::ToTokens::to_token_stream(predicate).to_string())
+                })
+                .collect();
+            assert_eq!(
+                predicates,
+                vec![Some("all (unix , not (test))".to_string()), None]
+            );
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                #[cfg_attr(feature)]
+                #[cfg_attr(feature = 1, Table)]
+                struct User;
+            );
+            let set = CfgSet::new();
+            let context = AnnotationContext::default().with_cfg_attr(CfgAttrMode::Evaluate(set));
+            let errors: Vec<String> = AnnotationStructures::<Table>::from_attributes_with_context(
+                &derive_input.attrs,
+                &context,
+            )
+            .err()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+            assert_eq!(
+                errors,
+                vec!["expected `,`", "Value of cfg option must be a string"]
+            );
+        }
+    }
+
+    /// `Member` and `AnnotatedMember` of derive inputs.
+    mod members {
+        use super::*;
+
+        #[test]
+        fn test_members() {
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                struct User {
+                    #[Column]
+                    user_name: String,
+                    #[Column(name = "user_age")]
+                    #[Index]
+                    age: i32,
+                    id: i32,
+                }
+            );
+            let members = AnnotatedMember::<(
+                AnnotationStructures<Column>,
+                AnnotationStructures<Index>,
+            )>::from_derive_input(&derive_input)
+            .unwrap();
+            assert_eq!(members.len(), 3);
+            let (columns, indexes) = &members[0].annotations;
+            assert_eq!(members[0].member.name(), "user_name");
+            assert_eq!(columns.first().unwrap().name, "userName");
+            assert!(indexes.first().is_none());
+            let (columns, indexes) = &members[1].annotations;
+            assert_eq!(members[1].member.index, 1);
+            assert_eq!(columns.first().unwrap().name, "user_age");
+            assert_eq!(indexes.attrs.len(), 1);
+            assert!(members[2].annotations.0.first().is_none());
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                struct Pair(#[Column(name = "left")] i32, i32);
+            );
+            let members = Member::from_derive_input(&derive_input);
+            assert_eq!(members[0].name(), "0");
+            assert!(members[0].ident.is_none());
+            assert!(members[1].ty.is_some());
+            let columns: AnnotationStructures<Column> = members[0].annotations().unwrap();
+            assert_eq!(columns.first().unwrap().name, "left");
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                enum Status {
+                    #[Column]
+                    Active,
+                    Disabled(i32),
+                }
+            );
+            let members =
+                AnnotatedMember::<AnnotationStructures<Column>>::from_derive_input(&derive_input)
+                    .unwrap();
+            assert_eq!(members[0].member.name(), "Active");
+            assert!(members[0].member.ty.is_none());
+            assert_eq!(members[0].annotations.first().unwrap().name, "active");
+            assert_eq!(members[1].member.index, 1);
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                union Bits {
+                    #[Column]
+                    int: u32,
+                    float: f32,
+                }
+            );
+            assert_eq!(Member::from_derive_input(&derive_input).len(), 2);
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                struct Unit;
+            );
+            assert!(Member::from_derive_input(&derive_input).is_empty());
+
+            let derive_input: syn::DeriveInput = syn::parse_quote!(
+                struct User {
+                    #[Column(nmae = "a")]
+                    name: String,
+                    #[Column(nmae = "b")]
+                    age: i32,
+                }
+            );
+            let error =
+                AnnotatedMember::<AnnotationStructures<Column>>::from_derive_input(&derive_input)
+                    .err()
+                    .unwrap();
+            assert_eq!(error.into_iter().count(), 2);
+        }
+    }
+
+    /// Annotations read from syn 2 input.
+    mod syn2_bridge {
+        use super::*;
+
+        #[test]
+        fn test_syn2() {
+            use annotation_rs::syn2;
+
+            let attribute: syn2::Attribute = syn2::parse_quote!(
+                #[Simple(i32 = -1, u16 = 2, float = 1.0, string = "", enum2 = "aaa")]
+            );
+            let meta = meta::Meta::from_syn2_attribute(&attribute).unwrap();
+            let simple = Simple::parse_nested_meta(
+                meta.nested().unwrap(),
+                &AnnotationContext::default(),
+                Span::call_site(),
+            )
+            .ok()
+            .unwrap();
+            assert_eq!(simple.int32, -1);
+
+            let attribute: syn2::Attribute = syn2::parse_quote!(
+                #[orm(table = "user", simple(i32 = 1, u16 = 2, float = 1.0, string = "", enum2 = "aaa"))]
+            );
+            let mut table = None;
+            let mut nested = None;
+            attribute
+                .parse_nested_meta(|meta| {
+                    if meta.path.is_ident("table") {
+                        table = Some(meta.value()?.parse::<syn2::LitStr>()?.value());
+                    } else if meta.path.is_ident("simple") {
+                        let args = meta::parse_syn2_nested_meta(&meta)?;
+                        nested = Simple::parse_nested_meta(
+                            &args,
+                            &AnnotationContext::default(),
+                            Span::call_site(),
+                        )
+                        .ok();
+                    }
+                    Ok(())
+                })
+                .unwrap();
+            assert_eq!(table.as_deref(), Some("user"));
+            assert_eq!(nested.unwrap().unsigned16, 2);
+
+            let attribute: syn2::Attribute = syn2::parse_quote!(#[orm(simple(i32 = 1))]);
+            let error = attribute
+                .parse_nested_meta(|meta| {
+                    let args = meta::parse_syn2_nested_meta(&meta)?;
+                    Simple::parse_nested_meta(
+                        &args,
+                        &AnnotationContext::default(),
+                        Span::call_site(),
+                    )
+                    .map(|_| ())
+                    .map_err(annotation_rs::to_syn2_error)
+                })
+                .err()
+                .unwrap();
+            assert!(error.to_string().starts_with("Missing required key"));
+
+            let derive_input: syn2::DeriveInput = syn2::parse_quote!(
+                /// docs
+                #[Simple(i32 = 3, u16 = 2, float = 1.0, string = "", enum2 = "aaa")]
+                struct Wrapper<T> {
+                    #[Simple(i32 = 4, u16 = 2, float = 1.0, string = "", enum2 = "aaa")]
+                    inner: T,
+                }
+            );
+            let simple = AnnotationStructures::<Simple>::from_syn2_derive_input(&derive_input)
+                .unwrap()
+                .only_one()
+                .unwrap()
+                .unwrap();
+            assert_eq!(simple.int32, 3);
+            let context = AnnotationContext::from_syn2_derive_input(&derive_input).unwrap();
+            assert_eq!(context.item_name().as_deref(), Some("Wrapper"));
+            let simple = AnnotationStructures::<Simple>::from_syn2_attributes_with_context(
+                &derive_input.attrs,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(simple.attrs.len(), 1);
+
+            let converted: syn::DeriveInput = annotation_rs::from_syn2(&derive_input).unwrap();
+            let members = Member::from_derive_input(&converted);
+            let simple = members[0]
+                .annotations::<AnnotationStructures<Simple>>()
+                .unwrap()
+                .only_one()
+                .unwrap()
+                .unwrap();
+            assert_eq!(simple.int32, 4);
+        }
+    }
+
+    /// Annotations read without a schema as `Value`.
+    mod schemaless {
+        use super::*;
+
+        #[test]
+        fn test_value() {
+            let attr: syn::Attribute = syn::parse_quote!(#[Full(
+                object(i32 = -1, u16 = 2, float = 1.5, string = crate::h, enum2 = "aaa"),
+                vector("a", "b"),
+                map("content-type" => "aaa"),
+                map2(x("y")),
+                map3(),
+                unknown(1, flag = true)
+            )]);
+            let value = Value::from_attribute(&attr).unwrap();
+            assert_eq!(value.path().unwrap().to_string(), "Full");
+
+            let object = value.get("object").unwrap();
+            assert!(matches!(object, Value::Map(_)));
+            assert!(object.path().is_none());
+            assert_eq!(object.get("i32").unwrap().as_int(), Some(-1));
+            assert_eq!(object.get("float").unwrap().as_float(), Some(1.5));
+            assert_eq!(
+                object.get("string").unwrap().as_string().unwrap(),
+                "crate::h"
+            );
+            let vector: Vec<String> = value
+                .get("vector")
+                .unwrap()
+                .as_list()
+                .unwrap()
+                .iter()
+                .filter_map(Value::as_string)
+                .collect();
+            assert_eq!(vector, vec!["a", "b"]);
+            assert_eq!(
+                value
+                    .get("map")
+                    .unwrap()
+                    .get("content-type")
+                    .unwrap()
+                    .as_string()
+                    .unwrap(),
+                "aaa"
+            );
+            assert_eq!(value.get("map3").unwrap().as_list().unwrap().len(), 0);
+
+            let unknown = value.get("unknown").unwrap();
+            assert_eq!(unknown.path().unwrap().to_string(), "unknown");
+            assert_eq!(unknown.get("flag").unwrap().as_bool(), Some(true));
+            if let Value::Object(object) = unknown {
+                assert_eq!(object.values[0].as_int(), Some(1));
+            }
+
+            assert_eq!(
+                quote::ToTokens::to_token_stream(&value).to_string(),
+                quote::quote!(Full(
+                    object(
+                        i32 = -1,
+                        u16 = 2,
+                        float = 1.5,
+                        string = "crate::h",
+                        enum2 = "aaa"
+                    ),
+                    vector("a", "b"),
+                    map("content-type" = "aaa"),
+                    map2(x("y")),
+                    map3(),
+                    unknown(1, flag = true)
+                ))
+                .to_string()
+            );
+
+            let meta = value.to_meta().unwrap();
+            let errors = Full::parse_nested_meta(
+                meta.nested().unwrap(),
+                &AnnotationContext::default(),
+                Span::call_site(),
+            )
+            .err()
+            .unwrap();
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].to_string(), "Unexpected key `unknown`");
+
+            let meta: syn::Meta = syn::parse_quote!(Full(
+                object(i32 = 1, u16 = 2, float = 1.5, string = "h", enum2 = "aaa"),
+                vector("a", "b"),
+                map(accept = "variant_b"),
+                map2(x("y")),
+                map3()
+            ));
+            let full = Full::from_meta(&syn::parse2(Value::from(&meta).to_token_stream()).unwrap())
+                .unwrap();
+            assert_eq!(full.object.int32, 1);
+            assert_eq!(full.object.string, "h");
+            assert_eq!(full.vector, vec!["a", "b"]);
+            assert_eq!(full.map.get("accept"), Some(&TestEnum::VariantB));
+            assert_eq!(full.map2.get("x").unwrap().0, Some(String::from("y")));
+
+            let letter: syn::Attribute = syn::parse_quote!(#[Tuple = "a"]);
+            let letter: syn::Meta =
+                syn::parse2(Value::from_attribute(&letter).unwrap().to_token_stream()).unwrap();
+            let tuple = Tuple::from_meta(&letter).unwrap();
+            assert_eq!(tuple.0, Some(String::from("a")));
+
+            let flags: syn::Attribute =
+                syn::parse_quote!(#[Foo(inner, "a", nested(flag), size = 1)]);
+            let flags = Value::from_attribute(&flags).unwrap();
+            assert!(matches!(flags.get("inner"), Some(Value::Map(map)) if map.entries.is_empty()));
+            assert!(flags.get("nested").unwrap().get("flag").is_some());
+            if let Value::Object(object) = &flags {
+                assert_eq!(object.values.len(), 1);
+            }
+            assert_eq!(
+                quote::ToTokens::to_token_stream(&flags).to_string(),
+                quote::quote!(Foo("a", inner, nested(flag), size = 1)).to_string()
+            );
+
+            let error = value.get("vector").unwrap().to_meta().err().unwrap();
+            assert_eq!(error.to_string(), "Expected an annotation like `Foo(...)`");
+        }
+    }
+
+    /// An `AnnotationStructure` implemented by hand.
+    mod manual {
+        use super::*;
+
+        /// Implemented against the syn 1 entry points, parsed through the bridges of the trait.
+        struct Manual(Vec<String>);
+
+        impl AnnotationStructure for Manual {
+            fn get_path() -> Symbol {
+                Symbol::new("Manual")
+            }
+
+            fn from_meta(input: &syn::Meta) -> Result<Self, syn::Error> {
+                match input {
+                    syn::Meta::List(list) => {
+                        Self::from_attribute_args(list.nested.iter().cloned().collect())
+                    }
+                    _ => Ok(Manual(Vec::new())),
+                }
+            }
+
+            fn from_attribute_args(input: syn::AttributeArgs) -> Result<Self, syn::Error> {
+                Ok(Manual(
+                    input
+                        .iter()
+                        .map(|nested| nested.to_token_stream().to_string())
+                        .collect(),
+                ))
+            }
+        }
+
+        #[test]
+        fn test_manual_implementation() {
+            let meta: syn::Meta = syn::parse_quote!(Manual(a, b = 1));
+            let context = AnnotationContext::default();
+            let expected = vec!["a", "b = 1"];
+
+            assert_eq!(Manual::from_meta(&meta).unwrap().0, expected);
+            assert_eq!(parse::<Manual>(&meta, &context).0, expected);
+            let nested = meta::parse_args(quote::quote!(a, b = 1)).unwrap();
+            assert_eq!(
+                Manual::from_nested_meta_lenient(&nested, &context, Span::call_site())
+                    .0
+                    .unwrap()
+                    .0,
+                expected
+            );
+            let attribute: syn::Attribute = syn::parse_quote!(#[Manual(a, b = 1)]);
+            let manual = AnnotationStructures::<Manual>::from_attributes(&[attribute]).unwrap();
+            assert_eq!(manual.first().unwrap().0, expected);
+
+            // syn 1 has no string literals as keys in attribute arguments
+            let nested = meta::parse_args(quote::quote!("a" = 1)).unwrap();
+            let (manual, errors) =
+                Manual::from_nested_meta_lenient(&nested, &context, Span::call_site());
+            assert!(manual.is_none());
+            assert_eq!(errors.len(), 1);
+        }
+    }
+}
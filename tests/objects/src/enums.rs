@@ -18,3 +18,15 @@ pub fn test_enum() {
     assert_eq!(TestEnum::from_str("variant_b").unwrap(), TestEnum::VariantB);
     assert_eq!(TestEnum::from_str("variant_c").unwrap(), TestEnum::VariantC);
 }
+
+#[test]
+pub fn test_enum_suggestion() {
+    assert_eq!(
+        TestEnum::from_str("varient_c").unwrap_err().get_message(),
+        "Unexpected TestEnum value: varient_c\n\nhelp: did you mean `variant_c`?"
+    );
+    assert_eq!(
+        TestEnum::from_str("unknown").unwrap_err().get_message(),
+        "Unexpected TestEnum value: unknown"
+    );
+}
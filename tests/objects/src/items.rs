@@ -0,0 +1,179 @@
+#[cfg(test)]
+use crate::attributes::{Column, Index, Table};
+#[cfg(test)]
+use annotation_rs::{
+    AnnotatedGenericParam, AnnotatedItem, AnnotatedParam, AnnotationContext, AnnotationStructures,
+    ItemKind, Member,
+};
+
+#[test]
+pub fn test_items() {
+    let file: syn::File = syn::parse_quote!(
+        #[Table]
+        fn handler(#[Column] user_name: String, count: i32) {}
+
+        #[Table(name = "accounts")]
+        impl UserAccount {
+            #[Index(kind = "hash")]
+            const LIMIT: i32 = 1;
+
+            #[Index]
+            fn find(&self, #[Column(name = "id")] id: i32) {}
+        }
+
+        trait Repository {
+            #[Table]
+            fn save(&self);
+        }
+
+        mod inner {
+            #[Table]
+            type Alias = i32;
+        }
+
+        #[derive(Clone)]
+        struct Plain;
+    );
+    type Annotations = (
+        AnnotationStructures<Table>,
+        AnnotationStructures<Column>,
+        AnnotationStructures<Index>,
+    );
+    let items = AnnotatedItem::<Annotations>::from_items(&file.items).unwrap();
+    assert_eq!(items.len(), 5);
+
+    let handler = &items[0];
+    assert_eq!(handler.kind, ItemKind::Fn);
+    assert_eq!(handler.annotations.0.first().unwrap().name, "handler");
+    assert_eq!(handler.params.len(), 2);
+    assert_eq!(
+        handler.params[0].annotations.1.first().unwrap().name,
+        "userName"
+    );
+    assert!(handler.params[1].annotations.1.first().is_none());
+
+    let implementation = &items[1];
+    assert_eq!(implementation.kind, ItemKind::Impl);
+    assert!(implementation.ident.is_none());
+    assert_eq!(
+        implementation.annotations.0.first().unwrap().name,
+        "accounts"
+    );
+    assert_eq!(implementation.children.len(), 2);
+    let constant = &implementation.children[0];
+    assert_eq!(constant.kind, ItemKind::Const);
+    assert_eq!(constant.annotations.2.first().unwrap().kind, "hash");
+    let method = &implementation.children[1];
+    assert_eq!(method.kind, ItemKind::Method);
+    assert_eq!(method.ident.unwrap(), "find");
+    assert_eq!(method.params.len(), 2);
+    assert_eq!(method.params[1].index, 1);
+    assert_eq!(method.params[1].annotations.1.first().unwrap().name, "id");
+
+    let save = &items[2].children[0];
+    assert_eq!(save.kind, ItemKind::Method);
+    assert_eq!(save.annotations.0.first().unwrap().name, "save");
+
+    let alias = &items[3].children[0];
+    assert_eq!(alias.kind, ItemKind::Type);
+    assert_eq!(alias.annotations.0.first().unwrap().name, "alias");
+    assert_eq!(items[3].descendants().len(), 2);
+
+    assert!(items[4].annotations.0.first().is_none());
+
+    let item: syn::Item = syn::parse_quote!(
+        impl UserAccount {
+            #[Column(nmae = "id")]
+            fn id(#[Column(nmae = "id")] id: i32) {}
+        }
+    );
+    let errors: Vec<String> = AnnotatedItem::<AnnotationStructures<Column>>::from_item(&item)
+        .err()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.to_string())
+        .collect();
+    // the method has no field to take the default name from, the parameter has
+    assert_eq!(
+        errors,
+        vec![
+            "Unexpected key `nmae`\n\nhelp: did you mean `name`?",
+            "Missing required key `name` in annotation `Column`, other missing required keys: `label`",
+            "Unexpected key `nmae`\n\nhelp: did you mean `name`?",
+        ]
+    );
+}
+
+#[test]
+pub fn test_params() {
+    let item: syn::ItemFn = syn::parse_quote!(
+        fn handler<#[Column] T, #[Column(name = "life")] 'a, #[Index] const N: usize>(
+            #[Column] user_name: &'a T,
+            (left, right): (i32, i32),
+        ) {
+        }
+    );
+    let params = AnnotatedParam::<AnnotationStructures<Column>>::from_signature(&item.sig).unwrap();
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[0].ident().unwrap(), "user_name");
+    assert_eq!(params[0].annotations.first().unwrap().name, "userName");
+    assert_eq!(
+        quote::ToTokens::to_token_stream(params[0].ty.unwrap()).to_string(),
+        "& 'a T"
+    );
+    assert!(params[1].ident().is_none());
+    assert!(matches!(params[1].pat, Some(syn::Pat::Tuple(_))));
+
+    let method: syn::ImplItemMethod = syn::parse_quote!(
+        fn find(&self, id: i32) {}
+    );
+    let params =
+        AnnotatedParam::<AnnotationStructures<Column>>::from_signature(&method.sig).unwrap();
+    assert_eq!(params[0].ident().unwrap(), "self");
+    assert!(params[0].ty.is_none());
+    assert_eq!(params[1].ident().unwrap(), "id");
+
+    // members report receivers the same way
+    let function: syn::Item = syn::parse_quote!(
+        fn find(&self, id: i32) {}
+    );
+    let members = Member::from_item_with_context(&function, &AnnotationContext::default());
+    assert_eq!(members[0].ident.as_ref().unwrap(), "self");
+    assert_eq!(members[0].name(), "self");
+    assert_eq!(members[0].context.field_name().unwrap(), "self");
+    assert_eq!(members[1].name(), "id");
+
+    type Annotations = (AnnotationStructures<Column>, AnnotationStructures<Index>);
+    let generics = AnnotatedGenericParam::<Annotations>::from_generics(&item.sig.generics).unwrap();
+    assert_eq!(generics.len(), 3);
+    assert_eq!(generics[0].ident, "T");
+    assert_eq!(generics[0].annotations.0.first().unwrap().name, "t");
+    assert!(generics[1].is_lifetime());
+    assert_eq!(generics[1].ident, "a");
+    assert_eq!(generics[1].annotations.0.first().unwrap().name, "life");
+    assert_eq!(generics[2].index, 2);
+    assert!(generics[2].annotations.1.first().is_some());
+
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        struct Wrapper<#[Table] T>(T);
+    );
+    let generics =
+        AnnotatedGenericParam::<AnnotationStructures<Table>>::from_generics_with_context(
+            &derive_input.generics,
+            &AnnotationContext::from_derive_input(&derive_input),
+        )
+        .unwrap();
+    assert_eq!(generics[0].annotations.first().unwrap().name, "wrapper");
+
+    let item: syn::Item = syn::parse_quote!(
+        impl<#[Table] S> Repository<S> {
+            fn save(&self, #[Column] entity: S) {}
+        }
+    );
+    let item = AnnotatedItem::<Annotations>::from_item(&item).unwrap();
+    assert_eq!(item.generics.len(), 1);
+    assert!(item.generics[0].annotations.0.first().is_none());
+    let save = &item.children[0];
+    assert!(save.params[0].pat.is_none());
+    assert_eq!(save.params[1].annotations.0.first().unwrap().name, "entity");
+}
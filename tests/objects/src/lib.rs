@@ -1,2 +1,5 @@
 pub mod attributes;
 pub mod enums;
+pub mod items;
+pub mod scanner;
+pub mod targets;
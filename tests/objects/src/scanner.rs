@@ -0,0 +1,132 @@
+#[cfg(test)]
+use crate::attributes::{Column, Index, Table};
+#[cfg(test)]
+use annotation_rs::{
+    AnnotationIndex, AnnotationStructures, CfgAttrMode, CfgSet, ItemKind, Scanner,
+};
+
+#[cfg(test)]
+type Annotations = (
+    AnnotationStructures<Table>,
+    AnnotationStructures<Column>,
+    AnnotationStructures<Index>,
+);
+
+#[cfg(test)]
+fn scan(scanner: Scanner) -> AnnotationIndex<Annotations> {
+    scanner.scan().unwrap()
+}
+
+#[cfg(test)]
+fn fixture() -> Scanner {
+    Scanner::new(concat!(env!("CARGO_MANIFEST_DIR"), "/scan_fixture"))
+}
+
+#[test]
+pub fn test_scanner_paths() {
+    let index = scan(fixture());
+    let paths: Vec<&str> = index
+        .entries
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "crate::api::users::User",
+            "crate::api::users::User::user_name",
+            "crate::api::users::User::find",
+            "crate::api::users::roles::UserRole",
+            "crate::api::admin::Admin",
+            "crate::models::Role::Admin",
+            "crate::Session",
+            "crate::Session::id",
+        ]
+    );
+
+    let user = index.get("crate::api::users::User").unwrap();
+    assert_eq!(user.kind, ItemKind::Struct);
+    assert!(user.file.ends_with("api/users.rs"));
+    assert_eq!(user.annotations.0.first().unwrap().name, "users");
+
+    let user_name = index.get("crate::api::users::User::user_name").unwrap();
+    assert_eq!(user_name.kind, ItemKind::Field);
+    assert_eq!(user_name.annotations.1.first().unwrap().name, "userName");
+
+    let find = index.get("crate::api::users::User::find").unwrap();
+    assert_eq!(find.kind, ItemKind::Method);
+    assert_eq!(find.annotations.2.first().unwrap().kind, "btree");
+
+    // `#[path]` in `api/users.rs` is relative to `api`, not to `api/users`
+    let user_role = index.get("crate::api::users::roles::UserRole").unwrap();
+    assert!(user_role.file.ends_with("api/user_roles.rs"));
+
+    let admin = index.get("crate::models::Role::Admin").unwrap();
+    assert_eq!(admin.kind, ItemKind::Variant);
+    assert!(admin.file.ends_with("extra/models.rs"));
+
+    assert_eq!(
+        index
+            .get("crate::Session")
+            .unwrap()
+            .annotations
+            .0
+            .first()
+            .unwrap()
+            .name,
+        "session"
+    );
+}
+
+#[test]
+pub fn test_scanner_cfg_attr() {
+    let set = CfgSet::new().with_value("feature", "db");
+    let index = scan(fixture().with_cfg_attr(CfgAttrMode::Evaluate(set)));
+    let get = index.get("crate::api::users::get::get").unwrap();
+    assert_eq!(get.kind, ItemKind::Fn);
+    assert_eq!(get.annotations.2.first().unwrap().kind, "hash");
+
+    let index = scan(fixture().with_cfg_attr(CfgAttrMode::Evaluate(CfgSet::new())));
+    assert!(index.get("crate::api::users::get::get").is_none());
+}
+
+#[test]
+pub fn test_scanner_output() {
+    let index = scan(fixture());
+    let json = index.to_json();
+    assert!(
+        json.starts_with("[{\"path\":\"crate::api::users::User\",\"kind\":\"Struct\",\"file\":\"")
+    );
+    assert!(json.contains("\"annotations\":[{\"name\":\"Table\",\"args\":{\"name\":\"users\"}}]"));
+    assert!(json.contains("{\"path\":\"crate::api::users::User::find\",\"kind\":\"Method\""));
+    assert!(json.contains("\"annotations\":[{\"name\":\"Column\",\"args\":true}]"));
+
+    let rust = index.to_rust("annotation_index").unwrap().to_string();
+    assert!(rust.contains("pub fn annotation_index ()"));
+    assert!(rust.contains("\"crate::api::users::User\""));
+    assert!(rust.contains("Vec < objects :: attributes :: Table >"));
+    syn::parse_str::<syn::ItemFn>(&rust).unwrap();
+}
+
+#[test]
+pub fn test_scanner_errors() {
+    let dir = std::env::temp_dir().join("annotation_rs_scanner_errors");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("lib.rs"),
+        "mod missing;\n\n#[Table(name = 1)]\nstruct Invalid;\n",
+    )
+    .unwrap();
+    let errors: Vec<String> = Scanner::new(&dir)
+        .scan::<Annotations>()
+        .err()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.to_string())
+        .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].ends_with("lib.rs:1:5: File of module `missing` not found"));
+    assert!(errors[1].contains("lib.rs:3:16: "));
+}
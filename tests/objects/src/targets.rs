@@ -0,0 +1,153 @@
+use crate::attributes::{Column, Index, Table};
+use annotation_rs::{AnnotationTarget, Warning};
+use syn::{Generics, Ident, Type};
+
+#[derive(AnnotationTarget)]
+pub struct InputField {
+    pub ident: Option<Ident>,
+    pub ty: Type,
+    #[annotation]
+    pub column: Option<Column>,
+}
+
+#[derive(AnnotationTarget)]
+pub struct Input {
+    pub ident: Ident,
+    pub generics: Generics,
+    #[annotation]
+    pub table: Table,
+    #[annotation]
+    pub indexes: Vec<Index>,
+    pub fields: Vec<InputField>,
+    pub warnings: Vec<Warning>,
+}
+
+#[test]
+pub fn test_target() {
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        #[derive(Clone)]
+        #[Table]
+        #[Index(kind = "hash")]
+        #[Index(method = "hash")]
+        struct UserAccount<T> {
+            #[Column]
+            user_name: String,
+            data: T,
+        }
+    );
+    let input = Input::from_derive_input(&derive_input).unwrap();
+    assert_eq!(input.ident, "UserAccount");
+    assert_eq!(input.generics.params.len(), 1);
+    assert_eq!(input.table.name, "user_account");
+    assert_eq!(input.indexes.len(), 2);
+    assert_eq!(input.warnings.len(), 1);
+    assert_eq!(input.fields.len(), 2);
+    assert_eq!(input.fields[0].ident.as_ref().unwrap(), "user_name");
+    assert_eq!(input.fields[0].column.as_ref().unwrap().name, "userName");
+    assert!(input.fields[1].column.is_none());
+    assert!(matches!(input.fields[1].ty, Type::Path(_)));
+}
+
+#[test]
+pub fn test_target_errors() {
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        struct UserAccount {
+            #[Column]
+            #[Column]
+            name: String,
+        }
+    );
+    let errors: Vec<String> = Input::from_derive_input(&derive_input)
+        .err()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            "Missing annotation `Table`",
+            "Annotation `Column` may appear only once"
+        ]
+    );
+
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        enum Status {
+            Active,
+        }
+    );
+    let error = InputField::from_derive_input(&derive_input).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "This annotation target can't be read from a derive input"
+    );
+}
+
+#[derive(AnnotationTarget)]
+pub struct IndexedField {
+    #[annotation]
+    pub index: Option<Index>,
+}
+
+#[derive(AnnotationTarget)]
+pub struct IndexedInput {
+    pub fields: Vec<IndexedField>,
+    pub warnings: Vec<Warning>,
+}
+
+#[test]
+pub fn test_target_member_warnings() {
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        struct UserAccount {
+            #[Index(kind = "btree")]
+            id: i32,
+            #[Index(method = "hash")]
+            name: String,
+        }
+    );
+    let input = IndexedInput::from_derive_input(&derive_input).unwrap();
+    assert_eq!(input.fields.len(), 2);
+    assert_eq!(
+        input
+            .warnings
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "Redundant value for `kind`, it is the same as the default",
+            "`method` is deprecated: use `kind` instead"
+        ]
+    );
+}
+
+/// Fields named like the locals of the generated constructor.
+#[derive(AnnotationTarget)]
+pub struct LocalsField {
+    #[annotation]
+    pub context: Option<Column>,
+    #[annotation]
+    pub span: Vec<Index>,
+}
+
+#[derive(AnnotationTarget)]
+pub struct LocalsInput {
+    #[annotation]
+    pub errors: Table,
+    pub fields: Vec<LocalsField>,
+}
+
+#[test]
+pub fn test_target_local_names() {
+    let derive_input: syn::DeriveInput = syn::parse_quote!(
+        #[Table]
+        struct UserAccount {
+            #[Column]
+            #[Index]
+            user_name: String,
+        }
+    );
+    let input = LocalsInput::from_derive_input(&derive_input).unwrap();
+    assert_eq!(input.errors.name, "user_account");
+    assert_eq!(input.fields[0].context.as_ref().unwrap().name, "userName");
+    assert_eq!(input.fields[0].span.len(), 1);
+}
@@ -0,0 +1,11 @@
+use derive::Annotation;
+
+#[derive(Annotation)]
+pub struct Cycle {
+    #[field(default_from_field = "b")]
+    pub a: String,
+    #[field(default_from_field = "a")]
+    pub b: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use derive::Annotation;
+
+#[derive(Annotation)]
+pub struct Unknown {
+    #[field(default_from_field = "missing")]
+    pub a: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive::Annotation;
+use std::num::NonZeroU32;
+
+#[derive(Annotation)]
+pub struct NonZero {
+    #[field(default = 0)]
+    pub a: NonZeroU32,
+}
+
+fn main() {}
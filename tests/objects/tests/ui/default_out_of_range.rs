@@ -0,0 +1,9 @@
+use derive::Annotation;
+
+#[derive(Annotation)]
+pub struct OutOfRange {
+    #[field(default = 256)]
+    pub a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use derive::Annotation;
+
+#[derive(Annotation)]
+pub struct Mismatch {
+    #[field(default = "yes")]
+    pub a: bool,
+}
+
+fn main() {}